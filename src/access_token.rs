@@ -1,64 +1,84 @@
-use crate::{Result, constants, error::Error::InternalServer, response::Response};
+use crate::{
+    Result, constants, error::Error::InternalServer, redact, response::Response,
+    secret::SecretString,
+};
 use chrono::{DateTime, Duration, Utc};
 use reqwest::Client;
-use serde::{Deserialize, Deserializer};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use tracing::{debug, instrument};
 
-#[derive(Clone)]
+/// 访问令牌来源
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenSource {
+    /// 稳定版接口 `cgi-bin/stable_token`
+    Stable,
+    /// 普通接口 `cgi-bin/token`
+    Plain,
+    /// 外部令牌提供者，参见 [`TokenProvider`](crate::token_provider::TokenProvider)
+    External,
+}
+
+#[derive(Debug, Clone)]
 pub struct AccessToken {
-    pub access_token: String,
+    pub access_token: SecretString,
     pub expired_at: DateTime<Utc>,
-    pub force_refresh: Option<bool>,
+    pub source: TokenSource,
 }
 
-impl std::fmt::Debug for AccessToken {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("StableAccessToken")
-            .field("access_token", &"********")
-            .field("expired_at", &self.expired_at)
-            .field("force_refresh", &self.force_refresh)
-            .finish()
+impl AccessToken {
+    pub(crate) fn info(&self, last_error: Option<String>) -> TokenInfo {
+        TokenInfo {
+            expired_at: self.expired_at,
+            source: self.source,
+            last_error,
+        }
     }
 }
 
-#[derive(Deserialize)]
-pub(crate) struct AccessTokenBuilder {
-    pub access_token: String,
-    #[serde(
-        deserialize_with = "AccessTokenBuilder::deserialize_expired_at",
-        rename = "expires_in"
-    )]
+/// 访问令牌状态
+///
+/// 不包含令牌本身，可以直接写入日志或健康检查接口，参见 [`Client::token_info`]。
+///
+/// [`Client::token_info`]: crate::Client::token_info
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct TokenInfo {
+    /// 过期时间，尚未获取令牌时为客户端创建时间
     pub expired_at: DateTime<Utc>,
+    /// 令牌来源
+    pub source: TokenSource,
+    /// 最近一次刷新失败的错误信息，刷新成功后清空
+    pub last_error: Option<String>,
 }
 
-impl AccessTokenBuilder {
-    fn deserialize_expired_at<'de, D>(
-        deserializer: D,
-    ) -> std::result::Result<DateTime<Utc>, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        let seconds = Duration::seconds(i64::deserialize(deserializer)?);
-
-        Ok(Utc::now() + seconds)
-    }
+#[derive(Debug, Deserialize)]
+pub(crate) struct AccessTokenBuilder {
+    pub access_token: SecretString,
+    /// 有效期（秒）
+    pub expires_in: i64,
 }
 
-impl std::fmt::Debug for AccessTokenBuilder {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("AccessTokenBuilder")
-            .field("access_token", &"********")
-            .field("expired_at", &self.expired_at)
-            .finish()
+impl AccessTokenBuilder {
+    /// 以 `now` 为获取时间计算过期时间
+    pub(crate) fn build(self, now: DateTime<Utc>, source: TokenSource) -> AccessToken {
+        AccessToken {
+            access_token: self.access_token,
+            expired_at: now + Duration::seconds(self.expires_in),
+            source,
+        }
     }
 }
 
 /// 获取小程序全局唯一后台接口调用凭据（access_token）
 /// https://developers.weixin.qq.com/miniprogram/dev/api-backend/open-api/access-token/auth.getAccessToken.html
-#[instrument(skip(client))]
+#[instrument(
+    skip(client, url, secret),
+    fields(endpoint = constants::ACCESS_TOKEN_END_POINT, status, errcode, rid)
+)]
 pub(crate) async fn get_access_token(
     client: Client,
+    url: &str,
     appid: &str,
     secret: &str,
 ) -> Result<AccessTokenBuilder> {
@@ -68,20 +88,16 @@ pub(crate) async fn get_access_token(
     map.insert("appid", appid);
     map.insert("secret", secret);
 
-    let response = client
-        .get(constants::ACCESS_TOKEN_END_POINT)
-        .query(&map)
-        .send()
-        .await?;
+    let response = client.get(url).query(&map).send().await?;
 
-    debug!("response: {:#?}", response);
+    redact::response(&response);
 
     if response.status().is_success() {
         let res = response.json::<Response<AccessTokenBuilder>>().await?;
 
         let builder = res.extract()?;
 
-        debug!("access token builder: {:#?}", builder);
+        debug!(expires_in = builder.expires_in, "access token fetched");
 
         Ok(builder)
     } else {
@@ -91,9 +107,13 @@ pub(crate) async fn get_access_token(
 
 /// 获取小程序全局唯一后台接口调用凭据（access_token）
 /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/mp-access-token/getStableAccessToken.html
-#[instrument(skip(client, force_refresh))]
+#[instrument(
+    skip(client, url, secret, force_refresh),
+    fields(endpoint = constants::STABLE_ACCESS_TOKEN_END_POINT, status, errcode, rid)
+)]
 pub(crate) async fn get_stable_access_token(
     client: Client,
+    url: &str,
     appid: &str,
     secret: &str,
     force_refresh: impl Into<Option<bool>>,
@@ -105,25 +125,24 @@ pub(crate) async fn get_stable_access_token(
     map.insert("secret", secret.to_string());
 
     if let Some(force_refresh) = force_refresh.into() {
-        debug!("force_refresh: {}", force_refresh);
+        debug!(force_refresh, "force refresh stable access token");
 
         map.insert("force_refresh", force_refresh.to_string());
     }
 
-    let response = client
-        .post(constants::STABLE_ACCESS_TOKEN_END_POINT)
-        .json(&map)
-        .send()
-        .await?;
+    let response = client.post(url).json(&map).send().await?;
 
-    debug!("response: {:#?}", response);
+    redact::response(&response);
 
     if response.status().is_success() {
         let response = response.json::<Response<AccessTokenBuilder>>().await?;
 
         let builder = response.extract()?;
 
-        debug!("stable access token builder: {:#?}", builder);
+        debug!(
+            expires_in = builder.expires_in,
+            "stable access token fetched"
+        );
 
         Ok(builder)
     } else {
@@ -0,0 +1,291 @@
+//! 接口熔断模块
+//!
+//! 微信服务故障期间，每个请求都要等待完整的超时时间。通过
+//! [`ClientBuilder::circuit_breaker`] 开启熔断后，[`Client`] 按接口统计连续失败次数：
+//!
+//! - 连续出现网络错误、非 2xx 状态码或 [`Error::System`] 达到阈值时打开熔断器，
+//!   之后的请求直接返回 [`Error::CircuitOpen`]，不发送请求
+//! - 打开一段时间后进入半开状态，放行一个探测请求，成功则关闭熔断器，失败则重新打开
+//! - 微信返回的其他业务错误说明服务可用，不计入失败次数
+//!
+//! 熔断器状态可以通过 [`Client::circuit_status`] 获取，用于健康检查。
+//!
+//! # 示例
+//!
+//! ```no_run
+//! use chrono::Duration;
+//! use wechat_minapp::{Client, circuit_breaker::CircuitBreakerConfig};
+//!
+//! let client = Client::builder("app_id", "secret")
+//!     .circuit_breaker(CircuitBreakerConfig::new(5, Duration::seconds(30)))
+//!     .build();
+//! ```
+//!
+//! [`ClientBuilder::circuit_breaker`]: crate::ClientBuilder::circuit_breaker
+//! [`Client`]: crate::Client
+//! [`Client::circuit_status`]: crate::Client::circuit_status
+//! [`Error::System`]: crate::error::Error::System
+//! [`Error::CircuitOpen`]: crate::error::Error::CircuitOpen
+
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use std::{collections::BTreeMap, sync::Mutex};
+use tracing::warn;
+
+use crate::{Result, error::Error};
+
+/// 熔断器配置
+#[derive(Debug, Clone, Copy)]
+pub struct CircuitBreakerConfig {
+    failure_threshold: u32,
+    open_duration: Duration,
+}
+
+impl CircuitBreakerConfig {
+    /// # 参数
+    ///
+    /// - `failure_threshold`: 打开熔断器所需的连续失败次数，最小为 1
+    /// - `open_duration`: 熔断器打开后进入半开状态的等待时间
+    pub fn new(failure_threshold: u32, open_duration: Duration) -> Self {
+        CircuitBreakerConfig {
+            failure_threshold: failure_threshold.max(1),
+            open_duration,
+        }
+    }
+}
+
+impl Default for CircuitBreakerConfig {
+    /// 连续失败 5 次打开，30 秒后半开
+    fn default() -> Self {
+        CircuitBreakerConfig::new(5, Duration::seconds(30))
+    }
+}
+
+/// 熔断器状态
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    /// 正常放行请求
+    Closed,
+    /// 直接拒绝请求
+    Open,
+    /// 放行一个探测请求
+    HalfOpen,
+}
+
+/// 单个接口的熔断器状态
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct CircuitStatus {
+    /// 接口地址，与 [`constants`](crate::constants) 中的常量一致
+    pub endpoint: String,
+    pub state: CircuitState,
+    /// 连续失败次数
+    pub consecutive_failures: u32,
+    /// 最近一次打开的时间
+    pub opened_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug)]
+struct Circuit {
+    failures: u32,
+    opened_at: Option<DateTime<Utc>>,
+    /// 半开状态下探测请求的开始时间
+    probe_started_at: Option<DateTime<Utc>>,
+}
+
+impl Circuit {
+    fn state(&self, now: DateTime<Utc>, config: &CircuitBreakerConfig) -> CircuitState {
+        match self.opened_at {
+            None => CircuitState::Closed,
+            Some(opened_at) if now - opened_at < config.open_duration => CircuitState::Open,
+            Some(_) => CircuitState::HalfOpen,
+        }
+    }
+}
+
+/// 按接口维护的熔断器
+#[derive(Debug)]
+pub(crate) struct CircuitBreakers {
+    config: CircuitBreakerConfig,
+    circuits: Mutex<BTreeMap<&'static str, Circuit>>,
+}
+
+impl CircuitBreakers {
+    pub(crate) fn new(config: CircuitBreakerConfig) -> Self {
+        CircuitBreakers {
+            config,
+            circuits: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// 请求前检查熔断器，打开时返回 [`Error::CircuitOpen`]
+    ///
+    /// 半开状态下只放行一个探测请求；探测请求被取消时，
+    /// 等待 `open_duration` 后放行下一个探测请求。
+    pub(crate) fn acquire(&self, endpoint: &'static str, now: DateTime<Utc>) -> Result<()> {
+        let mut circuits = self.circuits.lock().unwrap_or_else(|e| e.into_inner());
+
+        let circuit = circuits.entry(endpoint).or_insert(Circuit {
+            failures: 0,
+            opened_at: None,
+            probe_started_at: None,
+        });
+
+        match circuit.state(now, &self.config) {
+            CircuitState::Closed => Ok(()),
+            CircuitState::HalfOpen
+                if circuit
+                    .probe_started_at
+                    .is_none_or(|started_at| now - started_at >= self.config.open_duration) =>
+            {
+                circuit.probe_started_at = Some(now);
+                Ok(())
+            }
+            _ => Err(Error::CircuitOpen(endpoint.to_string())),
+        }
+    }
+
+    /// 记录请求结果
+    pub(crate) fn record<T>(&self, endpoint: &'static str, result: &Result<T>, now: DateTime<Utc>) {
+        let mut circuits = self.circuits.lock().unwrap_or_else(|e| e.into_inner());
+
+        let Some(circuit) = circuits.get_mut(endpoint) else {
+            return;
+        };
+
+        match result {
+            // 被熔断器拒绝的请求未发送，不能用来判断服务是否恢复
+            Err(Error::CircuitOpen(_)) => {}
+            Err(e) if e.is_transient() => {
+                circuit.failures = circuit.failures.saturating_add(1);
+
+                let probing = circuit.probe_started_at.take().is_some();
+
+                if probing || circuit.failures == self.config.failure_threshold {
+                    warn!(endpoint, failures = circuit.failures, error = %e, "circuit opened");
+
+                    circuit.opened_at = Some(now);
+                }
+            }
+            _ => {
+                circuit.failures = 0;
+                circuit.opened_at = None;
+                circuit.probe_started_at = None;
+            }
+        }
+    }
+
+    pub(crate) fn status(&self, now: DateTime<Utc>) -> Vec<CircuitStatus> {
+        let circuits = self.circuits.lock().unwrap_or_else(|e| e.into_inner());
+
+        circuits
+            .iter()
+            .map(|(endpoint, circuit)| CircuitStatus {
+                endpoint: endpoint.to_string(),
+                state: circuit.state(now, &self.config),
+                consecutive_failures: circuit.failures,
+                opened_at: circuit.opened_at,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ENDPOINT: &str = "https://api.weixin.qq.com/test";
+
+    fn system_error() -> Result<()> {
+        Err(Error::System("system error".into()))
+    }
+
+    #[test]
+    fn test_open_and_half_open() {
+        let breakers = CircuitBreakers::new(CircuitBreakerConfig::new(2, Duration::seconds(30)));
+        let now = Utc::now();
+
+        for _ in 0..2 {
+            breakers.acquire(ENDPOINT, now).unwrap();
+            breakers.record(ENDPOINT, &system_error(), now);
+        }
+
+        assert_eq!(breakers.status(now)[0].state, CircuitState::Open);
+        assert!(matches!(
+            breakers.acquire(ENDPOINT, now),
+            Err(Error::CircuitOpen(_))
+        ));
+
+        // 半开状态只放行一个探测请求
+        let later = now + Duration::seconds(30);
+        assert_eq!(breakers.status(later)[0].state, CircuitState::HalfOpen);
+        breakers.acquire(ENDPOINT, later).unwrap();
+        assert!(breakers.acquire(ENDPOINT, later).is_err());
+
+        // 探测失败重新打开
+        breakers.record(ENDPOINT, &system_error(), later);
+        assert_eq!(breakers.status(later)[0].state, CircuitState::Open);
+
+        // 探测成功关闭
+        let later = later + Duration::seconds(30);
+        breakers.acquire(ENDPOINT, later).unwrap();
+        breakers.record(ENDPOINT, &Ok(()), later);
+
+        let status = &breakers.status(later)[0];
+        assert_eq!(status.state, CircuitState::Closed);
+        assert_eq!(status.consecutive_failures, 0);
+    }
+
+    #[test]
+    fn test_circuit_open_is_not_recorded() {
+        let breakers = CircuitBreakers::new(CircuitBreakerConfig::new(1, Duration::seconds(30)));
+        let now = Utc::now();
+
+        breakers.acquire(ENDPOINT, now).unwrap();
+        breakers.record(ENDPOINT, &system_error(), now);
+
+        let open = Err::<(), _>(Error::CircuitOpen(ENDPOINT.into()));
+        breakers.record(ENDPOINT, &open, now);
+
+        let status = &breakers.status(now)[0];
+        assert_eq!(status.state, CircuitState::Open);
+        assert_eq!(status.consecutive_failures, 1);
+    }
+
+    #[test]
+    fn test_business_error_resets_failures() {
+        let breakers = CircuitBreakers::new(CircuitBreakerConfig::new(2, Duration::seconds(30)));
+        let now = Utc::now();
+
+        breakers.acquire(ENDPOINT, now).unwrap();
+        breakers.record(ENDPOINT, &system_error(), now);
+        breakers.acquire(ENDPOINT, now).unwrap();
+        breakers.record::<()>(ENDPOINT, &Err(Error::InvalidCode("code".into())), now);
+        breakers.acquire(ENDPOINT, now).unwrap();
+        breakers.record(ENDPOINT, &system_error(), now);
+
+        assert_eq!(breakers.status(now)[0].state, CircuitState::Closed);
+    }
+
+    #[tokio::test]
+    async fn test_decode_error_is_not_transient() {
+        let base_url = crate::test_server::serve(|_| "not json".to_string()).await;
+        let decode = reqwest::get(base_url)
+            .await
+            .unwrap()
+            .json::<serde_json::Value>()
+            .await
+            .map_err(Error::from);
+        assert!(matches!(&decode, Err(Error::Reqwest(e)) if e.is_decode()));
+
+        let breakers = CircuitBreakers::new(CircuitBreakerConfig::new(1, Duration::seconds(30)));
+        let now = Utc::now();
+
+        breakers.acquire(ENDPOINT, now).unwrap();
+        breakers.record(ENDPOINT, &decode, now);
+
+        let status = &breakers.status(now)[0];
+        assert_eq!(status.state, CircuitState::Closed);
+        assert_eq!(status.consecutive_failures, 0);
+    }
+}
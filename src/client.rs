@@ -1,21 +1,30 @@
 use crate::{
     Result,
-    access_token::{AccessToken, get_access_token, get_stable_access_token},
+    access_token::{
+        AccessToken, TokenInfo, TokenSource, get_access_token, get_stable_access_token,
+    },
+    circuit_breaker::{CircuitBreakerConfig, CircuitBreakers, CircuitStatus},
+    clock::{Clock, SystemClock},
     constants,
     credential::{Credential, CredentialBuilder},
-    error::Error::InternalServer,
+    error::Error::{self, InternalServer},
+    login_cache::LoginCache,
+    redact,
     response::Response,
+    secret::SecretString,
+    token_provider::{BoxFuture, TokenProvider},
 };
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, Utc};
 use std::{
     collections::HashMap,
+    future::Future,
     sync::{
         Arc,
         atomic::{AtomicBool, Ordering},
     },
 };
-use tokio::sync::{Notify, RwLock};
-use tracing::{debug, instrument};
+use tokio::sync::{Notify, RwLock, RwLockWriteGuard};
+use tracing::{debug, instrument, warn};
 
 ///
 /// 提供与微信小程序后端 API 交互的核心功能，包括用户登录、访问令牌管理等。
@@ -93,49 +102,116 @@ impl Client {
     /// let client = Client::new("your_appid", "your_app_secret_here");
     /// ```
     pub fn new(app_id: &str, secret: &str) -> Self {
-        let client = reqwest::Client::new();
-
-        Self {
-            inner: Arc::new(ClientInner {
-                app_id: app_id.into(),
-                secret: secret.into(),
-                client,
-            }),
-            access_token: Arc::new(RwLock::new(AccessToken {
-                access_token: "".to_string(),
-                expired_at: Utc::now(),
-                force_refresh: None,
-            })),
-            refreshing: Arc::new(AtomicBool::new(false)),
-            notify: Arc::new(Notify::new()),
-            use_stable_token: true,
-        }
+        ClientBuilder::new(app_id, secret).build()
     }
 
     pub fn with_non_stable(app_id: &str, secret: &str) -> Self {
-        let client = reqwest::Client::new();
+        ClientBuilder::new(app_id, secret)
+            .use_stable_token(false)
+            .build()
+    }
 
-        Self {
-            inner: Arc::new(ClientInner {
-                app_id: app_id.into(),
-                secret: secret.into(),
-                client,
-            }),
-            access_token: Arc::new(RwLock::new(AccessToken {
-                access_token: "".to_string(),
-                expired_at: Utc::now(),
-                force_refresh: None,
-            })),
-            refreshing: Arc::new(AtomicBool::new(false)),
-            notify: Arc::new(Notify::new()),
-            use_stable_token: false,
-        }
+    /// 创建令牌提供者模式的客户端
+    ///
+    /// 客户端不持有 AppSecret，访问令牌由 `provider` 提供，
+    /// 此时 [`Client::login`] 与 [`Client::rotate_secret`] 不可用。
+    ///
+    /// # 示例
+    ///
+    /// ```
+    /// use wechat_minapp::{Client, token_provider::StaticTokenProvider};
+    ///
+    /// let client = Client::with_token_provider("app_id", StaticTokenProvider::new("access_token"));
+    /// ```
+    pub fn with_token_provider(app_id: &str, provider: impl TokenProvider + 'static) -> Self {
+        ClientBuilder::with_token_provider(app_id, provider).build()
+    }
+
+    /// 创建客户端构建器
+    ///
+    /// 用于自定义 HTTP 客户端、令牌类型、水印有效期等配置。
+    ///
+    /// # 示例
+    ///
+    /// ```
+    /// use wechat_minapp::Client;
+    /// use chrono::Duration;
+    ///
+    /// let client = Client::builder("your_appid", "your_app_secret_here")
+    ///     .use_stable_token(false)
+    ///     .watermark_max_age(Duration::minutes(5))
+    ///     .build();
+    /// ```
+    pub fn builder(app_id: &str, secret: &str) -> ClientBuilder {
+        ClientBuilder::new(app_id, secret)
+    }
+
+    /// 小程序 AppID
+    pub fn app_id(&self) -> &str {
+        &self.inner.app_id
+    }
+
+    pub(crate) fn watermark_max_age(&self) -> Duration {
+        self.inner.watermark_max_age
     }
 
     pub(crate) fn request(&self) -> &reqwest::Client {
         &self.inner.client
     }
 
+    pub(crate) fn now(&self) -> DateTime<Utc> {
+        self.inner.clock.now()
+    }
+
+    /// 检查令牌是否过期
+    ///
+    /// 添加安全边界，在令牌过期前 [`ClientBuilder::refresh_margin`] 就认为需要刷新
+    fn is_token_expired(&self, token: &AccessToken) -> bool {
+        token
+            .expired_at
+            .signed_duration_since(self.inner.clock.now())
+            < self.inner.refresh_margin
+    }
+
+    /// 通过 `endpoint` 对应的熔断器执行请求，未开启熔断时直接执行
+    ///
+    /// `endpoint` 为 [`constants`] 中的接口地址常量，用作熔断器的键。
+    pub(crate) async fn guarded<T, F>(&self, endpoint: &'static str, request: F) -> Result<T>
+    where
+        F: Future<Output = Result<T>>,
+    {
+        let Some(breakers) = &self.inner.circuit_breakers else {
+            return request.await;
+        };
+
+        breakers.acquire(endpoint, self.now())?;
+
+        let result = request.await;
+
+        breakers.record(endpoint, &result, self.now());
+
+        result
+    }
+
+    /// 各接口的熔断器状态，未开启熔断时返回空列表
+    ///
+    /// 只包含已经发送过请求的接口，可用于健康检查。
+    pub fn circuit_status(&self) -> Vec<CircuitStatus> {
+        self.inner
+            .circuit_breakers
+            .as_ref()
+            .map(|breakers| breakers.status(self.now()))
+            .unwrap_or_default()
+    }
+
+    /// 将 [`constants`] 中的接口地址替换为自定义的 API 域名，参见 [`ClientBuilder::base_url`]
+    pub(crate) fn url(&self, endpoint: &str) -> String {
+        match &self.inner.base_url {
+            Some(base_url) => endpoint.replacen(constants::API_BASE_URL, base_url, 1),
+            None => endpoint.to_string(),
+        }
+    }
+
     /// 用户登录凭证校验
     ///
     /// 通过微信前端获取的临时登录凭证 code，换取用户的唯一标识 OpenID 和会话密钥。
@@ -166,8 +242,8 @@ impl Client {
     ///     let credential = client.login(code).await?;
     ///
     ///     println!("用户OpenID: {}", credential.open_id());
-    ///     println!("会话密钥: {}", credential.session_key());
-    ///     
+    ///     println!("会话密钥: {}", credential.session_key().expose_secret());
+    ///
     ///     Ok(())
     /// }
     /// ```
@@ -175,33 +251,63 @@ impl Client {
     /// # API 文档
     ///
     /// [微信官方文档 - code2Session](https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/user-login/code2Session.html)
-    #[instrument(skip(self, code))]
+    ///
+    /// # 重复调用
+    ///
+    /// 同一个 code 只能使用一次，重复使用会返回 [`Error::CodeBeenUsed`]。
+    /// 通过 [`ClientBuilder::login_cache`] 开启登录缓存后，时间窗口内使用相同 code
+    /// 的并发或重复调用会共享同一次请求的结果。
+    ///
+    /// [`Error::CodeBeenUsed`]: crate::error::Error::CodeBeenUsed
+    #[instrument(
+        skip(self, code),
+        fields(endpoint = constants::AUTHENTICATION_END_POINT, status, errcode, rid)
+    )]
     pub async fn login(&self, code: &str) -> Result<Credential> {
-        debug!("code: {}", code);
+        self.require_secret("login")?;
 
+        match &self.inner.login_cache {
+            Some(cache) => cache
+                .cell(code, self.now())
+                .get_or_try_init(|| self.code_to_session(code))
+                .await
+                .cloned(),
+            None => self.code_to_session(code).await,
+        }
+    }
+
+    async fn code_to_session(&self, code: &str) -> Result<Credential> {
+        self.guarded(
+            constants::AUTHENTICATION_END_POINT,
+            self.with_secret(|secret| self.code_to_session_with(code, secret)),
+        )
+        .await
+    }
+
+    async fn code_to_session_with(&self, code: &str, secret: SecretString) -> Result<Credential> {
         let mut map: HashMap<&str, &str> = HashMap::new();
 
         map.insert("appid", &self.inner.app_id);
-        map.insert("secret", &self.inner.secret);
+        map.insert("secret", secret.expose_secret());
         map.insert("js_code", code);
         map.insert("grant_type", "authorization_code");
 
         let response = self
             .inner
             .client
-            .get(constants::AUTHENTICATION_END_POINT)
+            .get(self.url(constants::AUTHENTICATION_END_POINT))
             .query(&map)
             .send()
             .await?;
 
-        debug!("authentication response: {:#?}", response);
+        redact::response(&response);
 
         if response.status().is_success() {
             let response = response.json::<Response<CredentialBuilder>>().await?;
 
             let credential = response.extract()?.build();
 
-            debug!("credential: {:#?}", credential);
+            debug!(open_id = %redact::id(credential.open_id()), "login succeeded");
 
             Ok(credential)
         } else {
@@ -209,6 +315,9 @@ impl Client {
         }
     }
 
+    /// 获取调用接口使用的访问令牌
+    ///
+    /// 根据客户端配置选择稳定版令牌、普通令牌或 [`TokenProvider`] 提供的令牌。
     pub async fn token(&self) -> Result<String> {
         if self.use_stable_token {
             self.stable_access_token(None).await
@@ -252,65 +361,7 @@ impl Client {
     /// - 客户端会自动管理令牌刷新，无需手动处理
     /// - 多线程环境下安全
     pub async fn access_token(&self) -> Result<String> {
-        // 第一次检查：快速路径
-        {
-            let guard = self.access_token.read().await;
-            if !is_token_expired(&guard) {
-                return Ok(guard.access_token.clone());
-            }
-        }
-
-        // 使用CAS竞争刷新权
-        if self
-            .refreshing
-            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
-            .is_ok()
-        {
-            // 获得刷新权
-            match self.refresh_access_token().await {
-                Ok(token) => {
-                    self.refreshing.store(false, Ordering::Release);
-                    self.notify.notify_waiters();
-                    Ok(token)
-                }
-                Err(e) => {
-                    self.refreshing.store(false, Ordering::Release);
-                    self.notify.notify_waiters();
-                    Err(e)
-                }
-            }
-        } else {
-            // 等待其他线程刷新完成
-            self.notify.notified().await;
-            // 刷新完成后重新读取
-            let guard = self.access_token.read().await;
-            Ok(guard.access_token.clone())
-        }
-    }
-
-    async fn refresh_access_token(&self) -> Result<String> {
-        let mut guard = self.access_token.write().await;
-
-        if !is_token_expired(&guard) {
-            debug!("token already refreshed by another thread");
-            return Ok(guard.access_token.clone());
-        }
-
-        debug!("performing network request to refresh token");
-
-        let builder = get_access_token(
-            self.inner.client.clone(),
-            &self.inner.app_id,
-            &self.inner.secret,
-        )
-        .await?;
-
-        guard.access_token = builder.access_token.clone();
-        guard.expired_at = builder.expired_at;
-
-        debug!("fresh access token: {:#?}", guard);
-
-        Ok(guard.access_token.clone())
+        self.cached_token(false, None).await
     }
 
     /// 获取稳定版访问令牌
@@ -360,11 +411,15 @@ impl Client {
         &self,
         force_refresh: impl Into<Option<bool>> + Clone + Send,
     ) -> Result<String> {
+        self.cached_token(true, force_refresh.into()).await
+    }
+
+    async fn cached_token(&self, stable: bool, force_refresh: Option<bool>) -> Result<String> {
         // 第一次检查：快速路径
         {
             let guard = self.access_token.read().await;
-            if !is_token_expired(&guard) {
-                return Ok(guard.access_token.clone());
+            if !self.is_token_expired(&guard) {
+                return Ok(guard.access_token.expose_secret().to_string());
             }
         }
 
@@ -375,77 +430,846 @@ impl Client {
             .is_ok()
         {
             // 获得刷新权
-            match self.refresh_stable_access_token(force_refresh).await {
-                Ok(token) => {
-                    self.refreshing.store(false, Ordering::Release);
-                    self.notify.notify_waiters();
-                    Ok(token)
-                }
-                Err(e) => {
-                    self.refreshing.store(false, Ordering::Release);
-                    self.notify.notify_waiters();
-                    Err(e)
-                }
-            }
+            let result = self.refresh_token(stable, force_refresh).await;
+
+            self.refreshing.store(false, Ordering::Release);
+            self.notify.notify_waiters();
+
+            result
         } else {
             // 等待其他线程刷新完成
             self.notify.notified().await;
             // 刷新完成后重新读取
             let guard = self.access_token.read().await;
-            Ok(guard.access_token.clone())
+            Ok(guard.access_token.expose_secret().to_string())
         }
     }
 
-    async fn refresh_stable_access_token(
-        &self,
-        force_refresh: impl Into<Option<bool>> + Clone + Send,
-    ) -> Result<String> {
+    async fn refresh_token(&self, stable: bool, force_refresh: Option<bool>) -> Result<String> {
         // 1. Acquire the write lock. This blocks if another thread won CAS but is refreshing.
-        let mut guard = self.access_token.write().await;
+        let guard = self.access_token.write().await;
 
         // 2. Double-check expiration under the write lock (CRITICAL)
         // If another CAS-winner refreshed the token while we were waiting for the write lock,
         // we return the new token without performing a new network call.
-        if !is_token_expired(&guard) {
-            // Token is now fresh, return it
+        if !self.is_token_expired(&guard) {
             debug!("token already refreshed by another thread");
-            return Ok(guard.access_token.clone());
+            return Ok(guard.access_token.expose_secret().to_string());
         }
 
         // 3. Perform the network request since the token is still stale
         debug!("performing network request to refresh token");
 
-        let builder = get_stable_access_token(
-            self.inner.client.clone(),
-            &self.inner.app_id,
-            &self.inner.secret,
-            force_refresh,
-        )
-        .await?;
+        self.replace_token(guard, stable, force_refresh).await
+    }
+
+    /// 获取新令牌并写入缓存，触发刷新回调
+    ///
+    /// 调用者需持有令牌写锁；获取失败时保留原有令牌。
+    async fn replace_token(
+        &self,
+        mut guard: RwLockWriteGuard<'_, AccessToken>,
+        stable: bool,
+        force_refresh: Option<bool>,
+    ) -> Result<String> {
+        match self.fetch_token(stable, force_refresh).await {
+            Ok(token) => *guard = token,
+            Err(e) => {
+                let info = guard.info(Some(e.to_string()));
+                drop(guard);
+
+                self.inner.set_last_error(info.last_error.clone());
+                if let Some(hook) = &self.inner.hooks.refresh_failed {
+                    hook(&e, &info);
+                }
+
+                return Err(e);
+            }
+        }
+
+        debug!(expired_at = %guard.expired_at, source = ?guard.source, "access token refreshed");
+
+        let token = guard.access_token.clone();
+        let info = guard.info(None);
+        drop(guard);
+
+        self.inner.set_last_error(None);
+        if let Some(hook) = &self.inner.hooks.refreshed {
+            hook(&token, &info);
+        }
+
+        Ok(token.expose_secret().to_string())
+    }
+
+    /// 访问令牌状态
+    ///
+    /// 返回过期时间、来源和最近一次刷新失败的错误，不包含令牌本身。
+    ///
+    /// # 示例
+    ///
+    /// ```no_run
+    /// use wechat_minapp::Client;
+    ///
+    /// # async fn example(client: Client) {
+    /// let info = client.token_info().await;
+    ///
+    /// println!("expired at {}, source {:?}", info.expired_at, info.source);
+    /// # }
+    /// ```
+    pub async fn token_info(&self) -> TokenInfo {
+        self.access_token.read().await.info(self.inner.last_error())
+    }
+
+    /// 使当前访问令牌失效
+    ///
+    /// 下次调用接口时重新获取令牌，如微信返回 40001 或令牌在其他地方被刷新时调用。
+    /// 会触发 [`ClientBuilder::on_token_invalidated`] 回调。
+    pub async fn invalidate_token(&self) {
+        let info = {
+            let mut guard = self.access_token.write().await;
+            guard.expired_at = self.inner.clock.now();
+            guard.info(self.inner.last_error())
+        };
+
+        debug!("access token invalidated");
+
+        if let Some(hook) = &self.inner.hooks.invalidated {
+            hook(&info);
+        }
+    }
+
+    /// 不论缓存的令牌是否过期，立即向微信（或令牌提供者）重新获取令牌
+    ///
+    /// 用于 [`Client::diagnose`] 等需要确认凭证当前是否有效的场景。
+    /// 获取 `cgi-bin/token` 会使之前的令牌在 5 分钟后失效，因此新令牌同样写入缓存
+    /// 并触发刷新回调；获取失败时保留缓存的令牌。
+    pub(crate) async fn fresh_token(&self) -> Result<String> {
+        let guard = self.access_token.write().await;
+
+        self.replace_token(guard, self.use_stable_token, None).await
+    }
+
+    async fn fetch_token(&self, stable: bool, force_refresh: Option<bool>) -> Result<AccessToken> {
+        if let Some(provider) = &self.inner.token_provider {
+            let token = provider.fetch(force_refresh.unwrap_or(false)).await?;
+
+            return Ok(token.build(self.now()));
+        }
+
+        let (primary, secondary) = if stable {
+            (TokenEndpoint::Stable, TokenEndpoint::Plain)
+        } else {
+            (TokenEndpoint::Plain, TokenEndpoint::Stable)
+        };
+
+        match self.fetch_token_from(primary, force_refresh).await {
+            Err(e)
+                if self.inner.token_fallback
+                    && (e.is_transient() || matches!(e, Error::CircuitOpen(_))) =>
+            {
+                warn!(error = %e, endpoint = ?primary, fallback = ?secondary, "falling back to another token endpoint");
+
+                self.fetch_token_from(secondary, force_refresh).await
+            }
+            result => result,
+        }
+    }
+
+    async fn fetch_token_from(
+        &self,
+        endpoint: TokenEndpoint,
+        force_refresh: Option<bool>,
+    ) -> Result<AccessToken> {
+        let (builder, source) = match endpoint {
+            TokenEndpoint::Stable => {
+                let request = self.with_secret(|secret| async move {
+                    get_stable_access_token(
+                        self.inner.client.clone(),
+                        &self.url(constants::STABLE_ACCESS_TOKEN_END_POINT),
+                        &self.inner.app_id,
+                        secret.expose_secret(),
+                        force_refresh,
+                    )
+                    .await
+                });
+
+                let builder = self
+                    .guarded(constants::STABLE_ACCESS_TOKEN_END_POINT, request)
+                    .await?;
+
+                (builder, TokenSource::Stable)
+            }
+            TokenEndpoint::Plain => {
+                let request = self.with_secret(|secret| async move {
+                    get_access_token(
+                        self.inner.client.clone(),
+                        &self.url(constants::ACCESS_TOKEN_END_POINT),
+                        &self.inner.app_id,
+                        secret.expose_secret(),
+                    )
+                    .await
+                });
+
+                let builder = self
+                    .guarded(constants::ACCESS_TOKEN_END_POINT, request)
+                    .await?;
+
+                (builder, TokenSource::Plain)
+            }
+        };
+
+        Ok(builder.build(self.now(), source))
+    }
+
+    /// 当前访问令牌的来源
+    ///
+    /// 开启 [`ClientBuilder::token_fallback`] 后，可用于确认当前令牌是否来自备用接口。
+    pub async fn token_source(&self) -> TokenSource {
+        self.access_token.read().await.source
+    }
 
-        // 4. Update the token
-        guard.access_token = builder.access_token.clone();
-        guard.expired_at = builder.expired_at;
+    /// 轮换 AppSecret
+    ///
+    /// 在小程序后台重置 AppSecret 后调用，无需重建客户端，所有克隆的客户端同时生效。
+    ///
+    /// # 参数
+    ///
+    /// - `secret`: 新的 AppSecret
+    /// - `force_refresh`: 是否立即使用新的 AppSecret 刷新访问令牌
+    ///   - `true`: 丢弃当前令牌并立即刷新，可用于校验新的 AppSecret
+    ///   - `false`: 保留当前令牌直到过期，下次刷新时使用新的 AppSecret
+    ///
+    /// # 示例
+    ///
+    /// ```no_run
+    /// use wechat_minapp::Client;
+    ///
+    /// # async fn example(client: Client) -> wechat_minapp::Result<()> {
+    /// client.rotate_secret("new_app_secret", true).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip(self, secret))]
+    pub async fn rotate_secret(&self, secret: &str, force_refresh: bool) -> Result<()> {
+        self.require_secret("rotate_secret")?;
+
+        self.inner.set_secret(secret.into());
+
+        debug!("app secret rotated");
+
+        if force_refresh {
+            self.invalidate_token().await;
+
+            if self.use_stable_token {
+                self.stable_access_token(true).await?;
+            } else {
+                self.access_token().await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn require_secret(&self, operation: &str) -> Result<()> {
+        if self.inner.token_provider.is_some() {
+            return Err(Error::UnsupportedOperation(format!(
+                "令牌提供者模式不支持 {}",
+                operation
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// 使用当前的 AppSecret 执行请求
+    ///
+    /// 返回 AppSecret 错误或已冻结时，若配置了 [`ClientBuilder::secret_provider`]，
+    /// 则重新读取 AppSecret，读取到新值时重试一次。
+    async fn with_secret<T, F, Fut>(&self, f: F) -> Result<T>
+    where
+        F: Fn(SecretString) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let secret = self.inner.secret();
 
-        debug!("fresh access token: {:#?}", guard);
+        match f(secret.clone()).await {
+            Err(e @ (Error::InvalidSecret(_) | Error::SecretFrozen(_))) => {
+                let Some(provider) = &self.inner.secret_provider else {
+                    return Err(e);
+                };
+
+                let fresh = (provider.0)().await?;
+
+                if fresh.expose_secret() == secret.expose_secret() {
+                    return Err(e);
+                }
 
-        // Return the newly fetched token (cloned here for consistency)
-        Ok(guard.access_token.clone())
+                debug!("app secret reloaded from provider");
+
+                self.inner.set_secret(fresh.clone());
+
+                f(fresh).await
+            }
+            result => result,
+        }
     }
 }
 
 #[derive(Debug)]
 struct ClientInner {
     app_id: String,
-    secret: String,
+    secret: std::sync::RwLock<SecretString>,
+    secret_provider: Option<SecretProvider>,
+    base_url: Option<String>,
+    clock: Arc<dyn Clock>,
+    refresh_margin: Duration,
+    token_provider: Option<Arc<dyn TokenProvider>>,
+    token_fallback: bool,
+    hooks: TokenHooks,
+    circuit_breakers: Option<CircuitBreakers>,
+    last_error: std::sync::Mutex<Option<String>>,
     client: reqwest::Client,
+    watermark_max_age: Duration,
+    login_cache: Option<LoginCache>,
+}
+
+impl ClientInner {
+    fn last_error(&self) -> Option<String> {
+        self.last_error
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    fn set_last_error(&self, error: Option<String>) {
+        *self.last_error.lock().unwrap_or_else(|e| e.into_inner()) = error;
+    }
+
+    fn secret(&self) -> SecretString {
+        self.secret
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    fn set_secret(&self, secret: SecretString) {
+        *self.secret.write().unwrap_or_else(|e| e.into_inner()) = secret;
+    }
 }
 
-/// 检查令牌是否过期
+/// 微信访问令牌接口
+#[derive(Debug, Clone, Copy)]
+enum TokenEndpoint {
+    /// `cgi-bin/stable_token`
+    Stable,
+    /// `cgi-bin/token`
+    Plain,
+}
+
+/// AppSecret 读取回调，参见 [`ClientBuilder::secret_provider`]
+#[derive(Clone)]
+struct SecretProvider(Arc<dyn Fn() -> BoxFuture<'static, Result<SecretString>> + Send + Sync>);
+
+impl std::fmt::Debug for SecretProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SecretProvider")
+    }
+}
+
+type RefreshedHook = Arc<dyn Fn(&SecretString, &TokenInfo) + Send + Sync>;
+type RefreshFailedHook = Arc<dyn Fn(&Error, &TokenInfo) + Send + Sync>;
+type InvalidatedHook = Arc<dyn Fn(&TokenInfo) + Send + Sync>;
+
+/// 访问令牌生命周期回调，参见 [`ClientBuilder::on_token_refreshed`]
+#[derive(Clone, Default)]
+struct TokenHooks {
+    refreshed: Option<RefreshedHook>,
+    refresh_failed: Option<RefreshFailedHook>,
+    invalidated: Option<InvalidatedHook>,
+}
+
+impl std::fmt::Debug for TokenHooks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TokenHooks")
+            .field("refreshed", &self.refreshed.is_some())
+            .field("refresh_failed", &self.refresh_failed.is_some())
+            .field("invalidated", &self.invalidated.is_some())
+            .finish()
+    }
+}
+
+/// 客户端构建器
 ///
-/// 添加安全边界，在令牌过期前5分钟就认为需要刷新
-fn is_token_expired(token: &AccessToken) -> bool {
-    // 添加安全边界，提前刷新
-    let now = Utc::now();
-    token.expired_at.signed_duration_since(now) < Duration::minutes(5)
+/// 通过 [`Client::builder`] 创建，未设置的选项使用默认值：
+///
+/// - 使用稳定版访问令牌
+/// - 使用默认配置的 `reqwest::Client`
+/// - 开放数据水印有效期 10 分钟
+/// - 不开启登录缓存
+#[derive(Debug)]
+pub struct ClientBuilder {
+    app_id: String,
+    secret: SecretString,
+    use_stable_token: bool,
+    http_client: Option<reqwest::Client>,
+    watermark_max_age: Duration,
+    login_cache: Option<Duration>,
+    secret_provider: Option<SecretProvider>,
+    base_url: Option<String>,
+    clock: Arc<dyn Clock>,
+    refresh_margin: Duration,
+    token_provider: Option<Arc<dyn TokenProvider>>,
+    token_fallback: bool,
+    hooks: TokenHooks,
+    circuit_breaker: Option<CircuitBreakerConfig>,
+}
+
+impl ClientBuilder {
+    pub fn new(app_id: &str, secret: &str) -> Self {
+        ClientBuilder {
+            app_id: app_id.into(),
+            secret: secret.into(),
+            use_stable_token: true,
+            http_client: None,
+            watermark_max_age: Duration::minutes(10),
+            login_cache: None,
+            secret_provider: None,
+            base_url: None,
+            clock: Arc::new(SystemClock),
+            refresh_margin: Duration::minutes(5),
+            token_provider: None,
+            token_fallback: false,
+            hooks: TokenHooks::default(),
+            circuit_breaker: None,
+        }
+    }
+
+    /// 创建令牌提供者模式的构建器，客户端不持有 AppSecret
+    ///
+    /// 参见 [`token_provider`](crate::token_provider) 模块
+    pub fn with_token_provider(app_id: &str, provider: impl TokenProvider + 'static) -> Self {
+        ClientBuilder {
+            token_provider: Some(Arc::new(provider)),
+            ..ClientBuilder::new(app_id, "")
+        }
+    }
+
+    /// 是否使用稳定版访问令牌
+    pub fn use_stable_token(mut self, use_stable_token: bool) -> Self {
+        self.use_stable_token = use_stable_token;
+        self
+    }
+
+    /// 使用自定义的 HTTP 客户端（如配置代理、超时等）
+    pub fn http_client(mut self, client: reqwest::Client) -> Self {
+        self.http_client = Some(client);
+        self
+    }
+
+    /// 设置开放数据水印的有效期，参见 [`Client::verify_watermark`]
+    pub fn watermark_max_age(mut self, max_age: Duration) -> Self {
+        self.watermark_max_age = max_age;
+        self
+    }
+
+    /// 开启登录缓存，参见 [`Client::login`]
+    ///
+    /// `ttl` 为缓存时间窗口，建议设置为前端登录请求的最大重试间隔，如 30 秒。
+    pub fn login_cache(mut self, ttl: Duration) -> Self {
+        self.login_cache = Some(ttl);
+        self
+    }
+
+    /// 设置 AppSecret 读取回调
+    ///
+    /// 获取访问令牌或登录时返回 AppSecret 错误（40125）或已冻结（40243），
+    /// 会调用该回调异步重新读取 AppSecret（如从文件或密钥管理服务读取），
+    /// 读取到新值时自动替换并重试一次。
+    ///
+    /// ```no_run
+    /// use wechat_minapp::{Client, secret::SecretString};
+    ///
+    /// let client = Client::builder("app_id", "secret")
+    ///     .secret_provider(|| async {
+    ///         let secret = tokio::fs::read_to_string("/run/secrets/app_secret").await?;
+    ///         Ok(SecretString::from(secret.trim()))
+    ///     })
+    ///     .build();
+    /// ```
+    pub fn secret_provider<F, Fut>(mut self, provider: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<SecretString>> + Send + 'static,
+    {
+        self.secret_provider = Some(SecretProvider(Arc::new(move || Box::pin(provider()))));
+        self
+    }
+
+    /// 使用自定义的 API 域名，如内网代理或测试用的模拟服务器
+    ///
+    /// 所有接口地址中的 `https://api.weixin.qq.com` 会被替换为 `base_url`。
+    pub fn base_url(mut self, base_url: &str) -> Self {
+        self.base_url = Some(base_url.trim_end_matches('/').to_string());
+        self
+    }
+
+    /// 使用自定义时钟，参见 [`clock`](crate::clock) 模块
+    pub fn clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// 设置访问令牌的刷新提前量，默认 5 分钟
+    ///
+    /// 令牌剩余有效期小于该值时即视为过期并刷新，主机时钟存在偏差时可以适当调大。
+    pub fn refresh_margin(mut self, margin: Duration) -> Self {
+        self.refresh_margin = margin;
+        self
+    }
+
+    /// 开启稳定版与普通访问令牌接口之间的自动切换
+    ///
+    /// 首选接口返回系统繁忙、非 2xx 状态码、网络错误或熔断器已打开时，改用另一个接口获取令牌，
+    /// 当前令牌的来源可以通过 [`Client::token_source`] 查询。AppSecret 错误等不会触发切换。
+    pub fn token_fallback(mut self, enabled: bool) -> Self {
+        self.token_fallback = enabled;
+        self
+    }
+
+    /// 开启按接口的熔断，参见 [`circuit_breaker`](crate::circuit_breaker) 模块
+    pub fn circuit_breaker(mut self, config: CircuitBreakerConfig) -> Self {
+        self.circuit_breaker = Some(config);
+        self
+    }
+
+    /// 设置访问令牌刷新成功的回调
+    ///
+    /// 回调在刷新完成、释放令牌锁之后同步执行，参数为新的令牌和令牌状态。
+    /// 耗时操作（如推送到其他服务）应当在回调中自行 `spawn`。
+    ///
+    /// ```no_run
+    /// use wechat_minapp::Client;
+    ///
+    /// let client = Client::builder("app_id", "secret")
+    ///     .on_token_refreshed(|token, info| {
+    ///         let token = token.expose_secret().to_string();
+    ///         tracing::info!(expired_at = %info.expired_at, "access token rotated");
+    ///         tokio::spawn(async move {
+    ///             // 推送 token 到边缘节点
+    ///         });
+    ///     })
+    ///     .build();
+    /// ```
+    pub fn on_token_refreshed<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&SecretString, &TokenInfo) + Send + Sync + 'static,
+    {
+        self.hooks.refreshed = Some(Arc::new(hook));
+        self
+    }
+
+    /// 设置访问令牌刷新失败的回调
+    ///
+    /// 参数为刷新错误和刷新前的令牌状态，错误同时会返回给触发刷新的调用者。
+    pub fn on_token_refresh_failed<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&Error, &TokenInfo) + Send + Sync + 'static,
+    {
+        self.hooks.refresh_failed = Some(Arc::new(hook));
+        self
+    }
+
+    /// 设置访问令牌失效的回调，参见 [`Client::invalidate_token`]
+    pub fn on_token_invalidated<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&TokenInfo) + Send + Sync + 'static,
+    {
+        self.hooks.invalidated = Some(Arc::new(hook));
+        self
+    }
+
+    pub fn build(self) -> Client {
+        let source = if self.token_provider.is_some() {
+            TokenSource::External
+        } else if self.use_stable_token {
+            TokenSource::Stable
+        } else {
+            TokenSource::Plain
+        };
+
+        Client {
+            inner: Arc::new(ClientInner {
+                app_id: self.app_id,
+                secret: std::sync::RwLock::new(self.secret),
+                secret_provider: self.secret_provider,
+                base_url: self.base_url,
+                clock: self.clock.clone(),
+                refresh_margin: self.refresh_margin,
+                token_provider: self.token_provider,
+                token_fallback: self.token_fallback,
+                hooks: self.hooks,
+                circuit_breakers: self.circuit_breaker.map(CircuitBreakers::new),
+                last_error: std::sync::Mutex::new(None),
+                client: self.http_client.unwrap_or_default(),
+                watermark_max_age: self.watermark_max_age,
+                login_cache: self.login_cache.map(LoginCache::new),
+            }),
+            access_token: Arc::new(RwLock::new(AccessToken {
+                access_token: SecretString::default(),
+                expired_at: self.clock.now(),
+                source,
+            })),
+            refreshing: Arc::new(AtomicBool::new(false)),
+            notify: Arc::new(Notify::new()),
+            use_stable_token: self.use_stable_token,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        access_token::AccessTokenBuilder, circuit_breaker::CircuitState, clock::ManualClock,
+        token_provider::StaticTokenProvider,
+    };
+
+    async fn fetch(secret: SecretString) -> Result<String> {
+        match secret.expose_secret() {
+            "new_secret" => Ok("token".to_string()),
+            _ => Err(Error::InvalidSecret("invalid secret".to_string())),
+        }
+    }
+
+    #[test]
+    fn test_base_url() {
+        let client = Client::new("app_id", "secret");
+        assert_eq!(
+            client.url(constants::ACCESS_TOKEN_END_POINT),
+            constants::ACCESS_TOKEN_END_POINT
+        );
+
+        let client = Client::builder("app_id", "secret")
+            .base_url("http://127.0.0.1:8080/")
+            .build();
+        assert_eq!(
+            client.url(constants::ACCESS_TOKEN_END_POINT),
+            "http://127.0.0.1:8080/cgi-bin/token"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_token_expiry_with_manual_clock() {
+        let clock = ManualClock::new(Utc::now());
+        let client = Client::builder("app_id", "secret")
+            .use_stable_token(false)
+            .clock(clock.clone())
+            .refresh_margin(Duration::minutes(10))
+            .build();
+
+        let builder: AccessTokenBuilder =
+            serde_json::from_str(r#"{ "access_token": "token", "expires_in": 7200 }"#).unwrap();
+        *client.access_token.write().await = builder.build(clock.now(), TokenSource::Plain);
+
+        assert_eq!(client.access_token().await.unwrap(), "token");
+
+        // 距离过期还剩 11 分钟，未进入刷新提前量
+        clock.advance(Duration::minutes(109));
+        assert!(!client.is_token_expired(&*client.access_token.read().await));
+        assert_eq!(client.access_token().await.unwrap(), "token");
+
+        // 距离过期还剩 9 分钟，需要刷新
+        clock.advance(Duration::minutes(2));
+        assert!(client.is_token_expired(&*client.access_token.read().await));
+    }
+
+    #[tokio::test]
+    async fn test_token_provider() {
+        let client = Client::with_token_provider("app_id", StaticTokenProvider::new("token"));
+
+        assert_eq!(client.token().await.unwrap(), "token");
+        assert_eq!(client.access_token().await.unwrap(), "token");
+        assert!(matches!(
+            client.login("code").await,
+            Err(Error::UnsupportedOperation(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_rotate_secret() {
+        let client = Client::new("app_id", "old_secret");
+        let cloned = client.clone();
+
+        client.rotate_secret("new_secret", false).await.unwrap();
+
+        assert_eq!(cloned.inner.secret().expose_secret(), "new_secret");
+        assert_eq!(cloned.with_secret(fetch).await.unwrap(), "token");
+    }
+
+    #[tokio::test]
+    async fn test_secret_provider() {
+        let client = Client::builder("app_id", "old_secret").build();
+        assert!(matches!(
+            client.with_secret(fetch).await,
+            Err(Error::InvalidSecret(_))
+        ));
+
+        let client = Client::builder("app_id", "old_secret")
+            .secret_provider(|| async { Ok(SecretString::from("new_secret")) })
+            .build();
+
+        assert_eq!(client.with_secret(fetch).await.unwrap(), "token");
+        assert_eq!(client.inner.secret().expose_secret(), "new_secret");
+    }
+
+    /// 本地模拟微信接口：稳定版接口返回系统繁忙，普通接口返回令牌
+    async fn serve_tokens() -> String {
+        crate::test_server::serve(|request| {
+            if request.contains("/cgi-bin/stable_token") {
+                r#"{"errcode":-1,"errmsg":"system error"}"#.to_string()
+            } else {
+                r#"{"access_token":"plain_token","expires_in":7200}"#.to_string()
+            }
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_token_fallback() {
+        let base_url = serve_tokens().await;
+
+        let client = Client::builder("app_id", "secret")
+            .base_url(&base_url)
+            .build();
+        assert!(matches!(
+            client.stable_access_token(None).await,
+            Err(Error::System(_))
+        ));
+
+        let client = Client::builder("app_id", "secret")
+            .base_url(&base_url)
+            .token_fallback(true)
+            .build();
+        assert_eq!(client.token_source().await, TokenSource::Stable);
+        assert_eq!(
+            client.stable_access_token(None).await.unwrap(),
+            "plain_token"
+        );
+        assert_eq!(client.token_source().await, TokenSource::Plain);
+    }
+
+    #[tokio::test]
+    async fn test_token_hooks() {
+        use std::sync::Mutex;
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+
+        let refreshed = events.clone();
+        let invalidated = events.clone();
+        let client =
+            ClientBuilder::with_token_provider("app_id", StaticTokenProvider::new("token"))
+                .on_token_refreshed(move |token, info| {
+                    refreshed.lock().unwrap().push(format!(
+                        "refreshed {} {:?}",
+                        token.expose_secret(),
+                        info.source
+                    ));
+                })
+                .on_token_invalidated(move |_| {
+                    invalidated.lock().unwrap().push("invalidated".into())
+                })
+                .build();
+
+        assert_eq!(client.token().await.unwrap(), "token");
+        client.invalidate_token().await;
+        assert_eq!(client.token().await.unwrap(), "token");
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            [
+                "refreshed token External",
+                "invalidated",
+                "refreshed token External"
+            ]
+        );
+
+        let info = client.token_info().await;
+        assert_eq!(info.source, TokenSource::External);
+        assert_eq!(info.last_error, None);
+        assert!(!format!("{:?}", info).contains("token\""));
+
+        let failed = events.clone();
+        let client = Client::builder("app_id", "secret")
+            .base_url(&serve_tokens().await)
+            .on_token_refresh_failed(move |e, _| failed.lock().unwrap().push(e.to_string()))
+            .build();
+
+        assert!(client.token().await.is_err());
+        let info = client.token_info().await;
+        assert!(info.last_error.is_some());
+        assert_eq!(events.lock().unwrap().last(), info.last_error.as_ref());
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker() {
+        let clock = ManualClock::new(Utc::now());
+        let client = Client::builder("app_id", "secret")
+            .base_url(&serve_tokens().await)
+            .clock(clock.clone())
+            .circuit_breaker(CircuitBreakerConfig::new(2, Duration::seconds(30)))
+            .build();
+
+        assert!(client.circuit_status().is_empty());
+
+        for _ in 0..2 {
+            assert!(matches!(client.token().await, Err(Error::System(_))));
+        }
+        assert!(matches!(client.token().await, Err(Error::CircuitOpen(_))));
+
+        let status = client.circuit_status();
+        assert_eq!(status[0].endpoint, constants::STABLE_ACCESS_TOKEN_END_POINT);
+        assert_eq!(status[0].state, CircuitState::Open);
+
+        clock.advance(Duration::seconds(30));
+        assert_eq!(client.circuit_status()[0].state, CircuitState::HalfOpen);
+    }
+
+    #[tokio::test]
+    async fn test_diagnose_refreshes_cached_token() {
+        let base_url = serve_tokens().await;
+        let builder = || {
+            serde_json::from_str::<AccessTokenBuilder>(
+                r#"{ "access_token": "token", "expires_in": 7200 }"#,
+            )
+            .unwrap()
+        };
+
+        // 稳定版接口返回错误：诊断失败，保留缓存的令牌
+        let client = Client::builder("app_id", "secret")
+            .base_url(&base_url)
+            .build();
+        *client.access_token.write().await = builder().build(client.now(), TokenSource::Stable);
+
+        let report = client.diagnose().await;
+        assert!(matches!(
+            report.credential,
+            crate::network::CheckStatus::Failed(_)
+        ));
+        assert_eq!(client.token().await.unwrap(), "token");
+
+        // 普通接口签发新令牌后旧令牌会失效，新令牌写入缓存并触发回调
+        let refreshed = Arc::new(AtomicBool::new(false));
+        let flag = refreshed.clone();
+        let client = Client::builder("app_id", "secret")
+            .base_url(&base_url)
+            .use_stable_token(false)
+            .on_token_refreshed(move |_, _| flag.store(true, Ordering::SeqCst))
+            .build();
+        *client.access_token.write().await = builder().build(client.now(), TokenSource::Plain);
+
+        let report = client.diagnose().await;
+        assert_eq!(report.credential, crate::network::CheckStatus::Passed);
+        assert_eq!(client.token().await.unwrap(), "plain_token");
+        assert!(refreshed.load(Ordering::SeqCst));
+    }
 }
@@ -0,0 +1,89 @@
+//! 时钟模块
+//!
+//! [`Client`](crate::Client) 通过 [`Clock`] 获取当前时间，用于访问令牌过期判断、
+//! 登录缓存过期和开放数据水印校验。默认使用系统时间 [`SystemClock`]，
+//! 测试时可以使用 [`ManualClock`] 手动推进时间。
+//!
+//! # 示例
+//!
+//! ```
+//! use chrono::{Duration, Utc};
+//! use wechat_minapp::{Client, clock::ManualClock};
+//!
+//! let clock = ManualClock::new(Utc::now());
+//!
+//! let client = Client::builder("app_id", "secret")
+//!     .clock(clock.clone())
+//!     .build();
+//!
+//! // 快进 2 小时，访问令牌将在下次调用时刷新
+//! clock.advance(Duration::hours(2));
+//! ```
+
+use chrono::{DateTime, Duration, Utc};
+use std::sync::{Arc, Mutex};
+
+/// 时钟
+pub trait Clock: Send + Sync + std::fmt::Debug {
+    /// 当前时间
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// 系统时钟
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// 手动时钟
+///
+/// 时间只在调用 [`ManualClock::set`] 或 [`ManualClock::advance`] 时变化，
+/// 克隆的实例共享同一个时间。
+#[derive(Debug, Clone)]
+pub struct ManualClock {
+    now: Arc<Mutex<DateTime<Utc>>>,
+}
+
+impl ManualClock {
+    pub fn new(now: DateTime<Utc>) -> Self {
+        ManualClock {
+            now: Arc::new(Mutex::new(now)),
+        }
+    }
+
+    /// 设置当前时间
+    pub fn set(&self, now: DateTime<Utc>) {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner()) = now;
+    }
+
+    /// 推进时间
+    pub fn advance(&self, duration: Duration) {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner()) += duration;
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manual_clock() {
+        let start = Utc::now();
+        let clock = ManualClock::new(start);
+        let cloned = clock.clone();
+
+        clock.advance(Duration::minutes(10));
+
+        assert_eq!(cloned.now(), start + Duration::minutes(10));
+    }
+}
@@ -0,0 +1,451 @@
+//! 客户端配置模块
+//!
+//! 提供可反序列化的 [`ClientConfig`]，用于从环境变量、TOML 或 JSON 配置文件创建 [`Client`]，
+//! 以及包含多个小程序配置的 [`AppsConfig`]。
+//!
+//! # 环境变量
+//!
+//! | 变量名 | 说明 |
+//! | --- | --- |
+//! | `WECHAT_APP_ID` | 小程序 AppID，必填 |
+//! | `WECHAT_APP_SECRET` | 小程序 AppSecret，必填 |
+//! | `WECHAT_TOKEN_MODE` | 令牌类型，`stable` 或 `plain`，默认 `stable` |
+//! | `WECHAT_BASE_URL` | 自定义 API 域名 |
+//! | `WECHAT_TIMEOUT` | 请求超时时间（秒） |
+//! | `WECHAT_CONNECT_TIMEOUT` | 连接超时时间（秒） |
+//! | `WECHAT_PROXY` | HTTP 代理地址，如 `http://127.0.0.1:8080` |
+//!
+//! 使用 [`ClientConfig::from_env_named`] 时，变量名前缀为 `WECHAT_{NAME}_`，如 `WECHAT_SHOP_APP_ID`。
+//!
+//! # 配置文件
+//!
+//! ```toml
+//! [apps.main]
+//! app_id = "wx1234567890"
+//! secret = "app_secret"
+//!
+//! [apps.shop]
+//! app_id = "wx0987654321"
+//! secret = "app_secret"
+//! token_mode = "plain"
+//! timeout = 10
+//! ```
+//!
+//! # 示例
+//!
+//! ```no_run
+//! use wechat_minapp::config::{AppsConfig, ClientConfig};
+//!
+//! # fn main() -> wechat_minapp::Result<()> {
+//! let client = ClientConfig::from_env()?.build()?;
+//!
+//! let apps = AppsConfig::from_file("wechat.toml")?;
+//! let shop = apps.build("shop")?;
+//! # Ok(())
+//! # }
+//! ```
+
+use serde::Deserialize;
+use std::{collections::BTreeMap, path::Path, time::Duration};
+
+use crate::{Client, ClientBuilder, Result, error::Error, secret::SecretString};
+
+/// 访问令牌类型
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenMode {
+    /// 稳定版访问令牌
+    #[default]
+    Stable,
+    /// 普通访问令牌
+    Plain,
+}
+
+impl TokenMode {
+    fn parse(value: &str) -> Result<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "stable" => Ok(TokenMode::Stable),
+            "plain" => Ok(TokenMode::Plain),
+            _ => Err(Error::InvalidParameter(format!(
+                "token_mode 只能为 stable 或 plain: {}",
+                value
+            ))),
+        }
+    }
+}
+
+/// 客户端配置
+///
+/// 通过 [`ClientConfig::build`] 创建 [`Client`]，创建前会校验配置。
+#[derive(Debug, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ClientConfig {
+    /// 小程序 AppID
+    pub app_id: String,
+    /// 小程序 AppSecret
+    pub secret: SecretString,
+    /// 访问令牌类型
+    #[serde(default)]
+    pub token_mode: TokenMode,
+    /// 自定义 API 域名，参见 [`ClientBuilder::base_url`]
+    #[serde(default)]
+    pub base_url: Option<String>,
+    /// 请求超时时间（秒）
+    #[serde(default)]
+    pub timeout: Option<u64>,
+    /// 连接超时时间（秒）
+    #[serde(default)]
+    pub connect_timeout: Option<u64>,
+    /// HTTP 代理地址
+    #[serde(default)]
+    pub proxy: Option<String>,
+}
+
+impl ClientConfig {
+    /// 从 `WECHAT_` 前缀的环境变量读取配置
+    pub fn from_env() -> Result<Self> {
+        Self::from_vars("WECHAT", |key| std::env::var(key).ok())
+    }
+
+    /// 从 `WECHAT_{NAME}_` 前缀的环境变量读取指定小程序的配置
+    ///
+    /// `name` 会被转换为大写，如 `shop` 对应 `WECHAT_SHOP_APP_ID`。
+    pub fn from_env_named(name: &str) -> Result<Self> {
+        let prefix = format!("WECHAT_{}", name.to_ascii_uppercase());
+
+        Self::from_vars(&prefix, |key| std::env::var(key).ok())
+    }
+
+    /// 从 TOML 字符串解析配置
+    pub fn from_toml_str(content: &str) -> Result<Self> {
+        toml::from_str(content).map_err(|e| Error::InvalidParameter(format!("配置解析失败: {}", e)))
+    }
+
+    /// 从 JSON 字符串解析配置
+    pub fn from_json_str(content: &str) -> Result<Self> {
+        serde_json::from_str(content)
+            .map_err(|e| Error::InvalidParameter(format!("配置解析失败: {}", e)))
+    }
+
+    /// 从配置文件读取配置，根据扩展名（`.toml` 或 `.json`）选择格式
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let (format, content) = read_file(path.as_ref())?;
+
+        match format {
+            Format::Toml => Self::from_toml_str(&content),
+            Format::Json => Self::from_json_str(&content),
+        }
+    }
+
+    /// 校验配置
+    ///
+    /// # 错误
+    ///
+    /// 配置无效时返回 [`Error::InvalidParameter`]
+    pub fn validate(&self) -> Result<()> {
+        if self.app_id.trim().is_empty() {
+            return Err(Error::InvalidParameter("app_id 不能为空".to_string()));
+        }
+
+        if self.secret.expose_secret().trim().is_empty() {
+            return Err(Error::InvalidParameter("secret 不能为空".to_string()));
+        }
+
+        if let Some(base_url) = &self.base_url
+            && !(base_url.starts_with("https://") || base_url.starts_with("http://"))
+        {
+            return Err(Error::InvalidParameter(format!(
+                "base_url 必须以 http:// 或 https:// 开头: {}",
+                base_url
+            )));
+        }
+
+        if self.timeout == Some(0) || self.connect_timeout == Some(0) {
+            return Err(Error::InvalidParameter("超时时间必须大于 0".to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// 校验配置并创建客户端构建器，可以继续设置登录缓存等选项
+    ///
+    /// # 错误
+    ///
+    /// 配置无效或无法创建 HTTP 客户端（如代理地址格式错误）时返回 [`Error::InvalidParameter`]
+    pub fn builder(&self) -> Result<ClientBuilder> {
+        self.validate()?;
+
+        let mut http_client = reqwest::Client::builder();
+
+        if let Some(timeout) = self.timeout {
+            http_client = http_client.timeout(Duration::from_secs(timeout));
+        }
+
+        if let Some(connect_timeout) = self.connect_timeout {
+            http_client = http_client.connect_timeout(Duration::from_secs(connect_timeout));
+        }
+
+        if let Some(proxy) = &self.proxy {
+            let proxy = reqwest::Proxy::all(proxy).map_err(|e| {
+                Error::InvalidParameter(format!("代理地址格式错误: {}: {}", proxy, e))
+            })?;
+            http_client = http_client.proxy(proxy);
+        }
+
+        let http_client = http_client
+            .build()
+            .map_err(|e| Error::InvalidParameter(e.to_string()))?;
+
+        let mut builder = Client::builder(&self.app_id, self.secret.expose_secret())
+            .use_stable_token(self.token_mode == TokenMode::Stable)
+            .http_client(http_client);
+
+        if let Some(base_url) = &self.base_url {
+            builder = builder.base_url(base_url);
+        }
+
+        Ok(builder)
+    }
+
+    /// 校验配置并创建客户端
+    pub fn build(&self) -> Result<Client> {
+        Ok(self.builder()?.build())
+    }
+
+    fn from_vars(prefix: &str, var: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        let get = |name: &str| var(&format!("{}_{}", prefix, name));
+
+        let required = |name: &str| {
+            get(name)
+                .ok_or_else(|| Error::InvalidParameter(format!("缺少环境变量 {}_{}", prefix, name)))
+        };
+
+        let seconds = |name: &str| {
+            get(name)
+                .map(|value| {
+                    value.parse::<u64>().map_err(|_| {
+                        Error::InvalidParameter(format!(
+                            "环境变量 {}_{} 必须为秒数: {}",
+                            prefix, name, value
+                        ))
+                    })
+                })
+                .transpose()
+        };
+
+        Ok(ClientConfig {
+            app_id: required("APP_ID")?,
+            secret: required("APP_SECRET")?.into(),
+            token_mode: get("TOKEN_MODE")
+                .map(|value| TokenMode::parse(&value))
+                .transpose()?
+                .unwrap_or_default(),
+            base_url: get("BASE_URL"),
+            timeout: seconds("TIMEOUT")?,
+            connect_timeout: seconds("CONNECT_TIMEOUT")?,
+            proxy: get("PROXY"),
+        })
+    }
+}
+
+/// 多个小程序的配置
+///
+/// 配置文件中的 `apps` 表，键为小程序名称。
+#[derive(Debug, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct AppsConfig {
+    apps: BTreeMap<String, ClientConfig>,
+}
+
+impl AppsConfig {
+    /// 从 TOML 字符串解析配置
+    pub fn from_toml_str(content: &str) -> Result<Self> {
+        let config: Self = toml::from_str(content)
+            .map_err(|e| Error::InvalidParameter(format!("配置解析失败: {}", e)))?;
+
+        config.validate()?;
+
+        Ok(config)
+    }
+
+    /// 从 JSON 字符串解析配置
+    pub fn from_json_str(content: &str) -> Result<Self> {
+        let config: Self = serde_json::from_str(content)
+            .map_err(|e| Error::InvalidParameter(format!("配置解析失败: {}", e)))?;
+
+        config.validate()?;
+
+        Ok(config)
+    }
+
+    /// 从配置文件读取配置，根据扩展名（`.toml` 或 `.json`）选择格式
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let (format, content) = read_file(path.as_ref())?;
+
+        match format {
+            Format::Toml => Self::from_toml_str(&content),
+            Format::Json => Self::from_json_str(&content),
+        }
+    }
+
+    /// 所有小程序名称
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.apps.keys().map(String::as_str)
+    }
+
+    /// 获取指定小程序的配置
+    pub fn get(&self, name: &str) -> Result<&ClientConfig> {
+        self.apps
+            .get(name)
+            .ok_or_else(|| Error::InvalidParameter(format!("未配置小程序: {}", name)))
+    }
+
+    /// 创建指定小程序的客户端
+    pub fn build(&self, name: &str) -> Result<Client> {
+        self.get(name)?.build()
+    }
+
+    /// 创建所有小程序的客户端
+    pub fn build_all(&self) -> Result<BTreeMap<String, Client>> {
+        self.apps
+            .iter()
+            .map(|(name, config)| Ok((name.clone(), config.build()?)))
+            .collect()
+    }
+
+    // 解析时校验所有配置，错误信息中包含小程序名称
+    fn validate(&self) -> Result<()> {
+        if self.apps.is_empty() {
+            return Err(Error::InvalidParameter("apps 不能为空".to_string()));
+        }
+
+        for (name, config) in &self.apps {
+            config.validate().map_err(|e| match e {
+                Error::InvalidParameter(message) => {
+                    Error::InvalidParameter(format!("apps.{}: {}", name, message))
+                }
+                e => e,
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+enum Format {
+    Toml,
+    Json,
+}
+
+fn read_file(path: &Path) -> Result<(Format, String)> {
+    let format = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("toml") => Format::Toml,
+        Some("json") => Format::Json,
+        _ => {
+            return Err(Error::InvalidParameter(format!(
+                "不支持的配置文件格式: {}",
+                path.display()
+            )));
+        }
+    };
+
+    Ok((format, std::fs::read_to_string(path)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_from_vars() {
+        let vars = HashMap::from([
+            ("WECHAT_SHOP_APP_ID", "wx123"),
+            ("WECHAT_SHOP_APP_SECRET", "secret"),
+            ("WECHAT_SHOP_TOKEN_MODE", "plain"),
+            ("WECHAT_SHOP_TIMEOUT", "10"),
+        ]);
+
+        let config =
+            ClientConfig::from_vars("WECHAT_SHOP", |key| vars.get(key).map(|v| v.to_string()))
+                .unwrap();
+
+        assert_eq!(config.app_id, "wx123");
+        assert_eq!(config.token_mode, TokenMode::Plain);
+        assert_eq!(config.timeout, Some(10));
+        assert!(config.build().is_ok());
+
+        let result = ClientConfig::from_vars("WECHAT", |key| vars.get(key).map(|v| v.to_string()));
+        assert!(matches!(result, Err(Error::InvalidParameter(_))));
+    }
+
+    #[test]
+    fn test_invalid_proxy() {
+        let vars = HashMap::from([
+            ("WECHAT_APP_ID", "wx123"),
+            ("WECHAT_APP_SECRET", "secret"),
+            ("WECHAT_PROXY", "http://[::1"),
+        ]);
+
+        let mut config =
+            ClientConfig::from_vars("WECHAT", |key| vars.get(key).map(|v| v.to_string())).unwrap();
+        assert!(matches!(config.builder(), Err(Error::InvalidParameter(_))));
+
+        config.proxy = Some("http://127.0.0.1:8080".to_string());
+        assert!(config.build().is_ok());
+    }
+
+    #[test]
+    fn test_apps_config() {
+        let toml = r#"
+            [apps.main]
+            app_id = "wx123"
+            secret = "secret"
+
+            [apps.shop]
+            app_id = "wx456"
+            secret = "secret"
+            token_mode = "plain"
+            base_url = "http://127.0.0.1:8080"
+        "#;
+
+        let apps = AppsConfig::from_toml_str(toml).unwrap();
+        assert_eq!(apps.names().collect::<Vec<_>>(), vec!["main", "shop"]);
+        assert_eq!(apps.build("shop").unwrap().app_id(), "wx456");
+        assert!(apps.build("other").is_err());
+
+        let json = r#"{ "apps": { "main": { "app_id": "wx123", "secret": "secret" } } }"#;
+        assert_eq!(
+            AppsConfig::from_json_str(json)
+                .unwrap()
+                .build_all()
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        let toml = r#"
+            [apps.main]
+            app_id = ""
+            secret = "secret"
+        "#;
+        assert!(matches!(
+            AppsConfig::from_toml_str(toml),
+            Err(Error::InvalidParameter(message)) if message.starts_with("apps.main")
+        ));
+
+        let config = ClientConfig::from_json_str(
+            r#"{ "app_id": "wx123", "secret": "secret", "base_url": "api.weixin.qq.com" }"#,
+        )
+        .unwrap();
+        assert!(matches!(config.build(), Err(Error::InvalidParameter(_))));
+
+        assert!(matches!(
+            ClientConfig::from_json_str(r#"{ "app_id": "wx123", "secret": "secret", "token": 1 }"#),
+            Err(Error::InvalidParameter(_))
+        ));
+    }
+}
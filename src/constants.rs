@@ -3,6 +3,10 @@
 //! 该模块定义了微信小程序所有官方 API 的端点 URL 常量。
 //! 这些常量用于构建完整的 API 请求地址，确保 URL 的正确性和一致性。
 //!
+//! 所有端点均以 [`API_BASE_URL`] 开头，可以通过 [`ClientBuilder::base_url`] 替换为自定义域名。
+//!
+//! [`ClientBuilder::base_url`]: crate::ClientBuilder::base_url
+//!
 //! # API 分类
 //!
 //! ## 访问令牌管理
@@ -14,21 +18,41 @@
 //!
 //! - [`CHECK_SESSION_KEY_END_POINT`] - 检查会话密钥有效性
 //! - [`RESET_SESSION_KEY_END_POINT`] - 重置用户会话密钥
+//! - [`CHECK_ENCRYPTED_DATA_END_POINT`] - 检查加密信息是否由微信生成
 //!
 //! ## 用户信息相关
 //!
 //! - [`PHONE_END_POINT`] - 获取用户手机号
 //! - [`AUTHENTICATION_END_POINT`] - 用户登录凭证校验
+//! - [`PAID_UNION_ID_END_POINT`] - 支付后获取用户 UnionID
+//! - [`PLUGIN_OPEN_PID_END_POINT`] - 获取插件用户 openpid
+//!
+//! ## 用户数据存储
+//!
+//! - [`USER_ENCRYPT_KEY_END_POINT`] - 获取用户加密密钥
+//! - [`SET_USER_STORAGE_END_POINT`] - 设置用户托管数据
+//! - [`REMOVE_USER_STORAGE_END_POINT`] - 删除用户托管数据
 //!
 //! ## 内容与媒体
 //!
 //! - [`QR_CODE_ENDPOINT`] - 生成小程序二维码
+//! - [`UNLIMITED_QR_CODE_ENDPOINT`] - 生成不限制数量的小程序码
+//! - [`CREATE_QR_CODE_ENDPOINT`] - 生成小程序二维码（方形）
 //! - [`MSG_SEC_CHECK_END_POINT`] - 内容安全检测
+//! - [`USER_RISK_RANK_END_POINT`] - 获取用户安全等级
+//!
+//! ## 网络诊断
+//!
+//! - [`CALLBACK_CHECK_END_POINT`] - 网络检测
+//! - [`API_DOMAIN_IP_END_POINT`] - 获取微信 API 服务器 IP
 //!
 //! # 版本信息
 //!
 //! 这些端点对应微信小程序最新的 API 版本，会随着微信官方 API 的更新而维护。
 
+/// 微信 API 域名
+pub const API_BASE_URL: &str = "https://api.weixin.qq.com";
+
 /// 获取稳定版访问令牌的 API 端点
 /// # 官方文档
 ///
@@ -54,6 +78,14 @@ pub const CHECK_SESSION_KEY_END_POINT: &str = "https://api.weixin.qq.com/wxa/che
 /// [重置用户会话密钥](https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/user-login/resetSessionKey.html)
 pub const RESET_SESSION_KEY_END_POINT: &str = "https://api.weixin.qq.com/wxa/resetusersessionkey";
 
+/// 检查加密信息是否由微信生成的 API 端点
+///
+/// # 官方文档
+///
+/// [检查加密信息](https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/user-info/basic-info/checkEncryptedData.html)
+pub const CHECK_ENCRYPTED_DATA_END_POINT: &str =
+    "https://api.weixin.qq.com/wxa/business/checkencryptedmsg";
+
 /// 获取用户手机号的 API 端点
 ///
 /// # 官方文档
@@ -68,6 +100,42 @@ pub const PHONE_END_POINT: &str = "https://api.weixin.qq.com/wxa/business/getuse
 /// [code2Session](https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/user-login/code2Session.html)
 pub const AUTHENTICATION_END_POINT: &str = "https://api.weixin.qq.com/sns/jscode2session";
 
+/// 支付后获取用户 UnionID 的 API 端点
+///
+/// # 官方文档
+///
+/// [支付后获取 UnionID](https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/user-info/basic-info/getPaidUnionid.html)
+pub const PAID_UNION_ID_END_POINT: &str = "https://api.weixin.qq.com/wxa/getpaidunionid";
+
+/// 获取插件用户 openpid 的 API 端点
+///
+/// # 官方文档
+///
+/// [获取插件用户 openpid](https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/user-info/basic-info/getPluginOpenPId.html)
+pub const PLUGIN_OPEN_PID_END_POINT: &str = "https://api.weixin.qq.com/wxa/getpluginopenpid";
+
+/// 获取用户加密密钥的 API 端点
+///
+/// # 官方文档
+///
+/// [获取用户encryptKey](https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/user-info/internet/getUserEncryptKey.html)
+pub const USER_ENCRYPT_KEY_END_POINT: &str =
+    "https://api.weixin.qq.com/wxa/business/getuserencryptkey";
+
+/// 设置用户托管数据的 API 端点
+///
+/// # 官方文档
+///
+/// [设置用户托管数据](https://developers.weixin.qq.com/minigame/dev/api-backend/open-api/data/storage.setUserStorage.html)
+pub const SET_USER_STORAGE_END_POINT: &str = "https://api.weixin.qq.com/wxa/set_user_storage";
+
+/// 删除用户托管数据的 API 端点
+///
+/// # 官方文档
+///
+/// [删除用户托管数据](https://developers.weixin.qq.com/minigame/dev/api-backend/open-api/data/storage.removeUserStorage.html)
+pub const REMOVE_USER_STORAGE_END_POINT: &str = "https://api.weixin.qq.com/wxa/remove_user_storage";
+
 /// 生成小程序小程序码的 API 端点
 ///
 /// # 官方文档
@@ -75,9 +143,45 @@ pub const AUTHENTICATION_END_POINT: &str = "https://api.weixin.qq.com/sns/jscode
 /// [获取小程序码](https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/qrcode-link/qr-code/getQRCode.html)
 pub const QR_CODE_ENDPOINT: &str = "https://api.weixin.qq.com/wxa/getwxacode";
 
+/// 生成不限制数量的小程序码的 API 端点
+///
+/// # 官方文档
+///
+/// [获取不限制的小程序码](https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/qrcode-link/qr-code/getUnlimitedQRCode.html)
+pub const UNLIMITED_QR_CODE_ENDPOINT: &str = "https://api.weixin.qq.com/wxa/getwxacodeunlimit";
+
+/// 生成小程序二维码（方形）的 API 端点
+///
+/// # 官方文档
+///
+/// [获取小程序二维码](https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/qrcode-link/qr-code/createQRCode.html)
+pub const CREATE_QR_CODE_ENDPOINT: &str =
+    "https://api.weixin.qq.com/cgi-bin/wxaapp/createwxaqrcode";
+
 /// 内容安全检测的 API 端点
 ///
 /// # 官方文档
 ///
 /// [文本安全检测](https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/sec-center/sec-check/msgSecCheck.html)
 pub const MSG_SEC_CHECK_END_POINT: &str = "https://api.weixin.qq.com/wxa/msg_sec_check";
+
+/// 获取用户安全等级的 API 端点
+///
+/// # 官方文档
+///
+/// [获取用户安全等级](https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/sec-center/safety-control-capability/getUserRiskRank.html)
+pub const USER_RISK_RANK_END_POINT: &str = "https://api.weixin.qq.com/wxa/getuserriskrank";
+
+/// 网络检测的 API 端点
+///
+/// # 官方文档
+///
+/// [网络检测](https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/openApi-mgnt/callbackCheck.html)
+pub const CALLBACK_CHECK_END_POINT: &str = "https://api.weixin.qq.com/cgi-bin/callback/check";
+
+/// 获取微信 API 服务器 IP 的 API 端点
+///
+/// # 官方文档
+///
+/// [获取微信API服务器IP](https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/openApi-mgnt/getApiDomainIp.html)
+pub const API_DOMAIN_IP_END_POINT: &str = "https://api.weixin.qq.com/cgi-bin/get_api_domain_ip";
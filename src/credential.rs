@@ -1,14 +1,14 @@
-#[allow(deprecated)]
 use aes::{
     Aes128,
-    cipher::{BlockDecryptMut, KeyIvInit, block_padding::Pkcs7, generic_array::GenericArray},
+    cipher::{BlockDecryptMut, BlockEncryptMut, KeyIvInit, block_padding::Pkcs7},
 };
 use base64::{Engine, engine::general_purpose::STANDARD};
-use cbc::Decryptor;
+use cbc::{Decryptor, Encryptor};
 use hex::encode;
-use hmac::{Hmac, Mac};
-use serde::{Deserialize, Serialize};
+use rand::RngCore;
+use serde::{Deserialize, Serialize, Serializer, de::DeserializeOwned, ser::SerializeStruct};
 use serde_json::from_slice;
+use sha1::{Digest, Sha1};
 use sha2::Sha256;
 use std::collections::HashMap;
 use tracing::{debug, instrument};
@@ -17,27 +17,92 @@ use crate::{
     Result,
     client::Client,
     constants,
-    error::Error::InternalServer,
-    response::Response,
-    user::{User, UserBuilder},
+    error::Error::{self, InternalServer},
+    redact,
+    response::{Response, Status},
+    secret::SecretString,
+    signature,
+    user::{
+        Contact, GroupInfo, GroupInfoBuilder, PhoneInner, User, UserBuilder, UserInfo,
+        UserInfoBuilder, WeRunData, WeRunDataBuilder,
+    },
 };
 
 type Aes128CbcDec = Decryptor<Aes128>;
+type Aes128CbcEnc = Encryptor<Aes128>;
+
+/// 加密后的开放数据
+///
+/// 与前端获取的 `encryptedData`、`iv` 格式相同，均为 Base64 编码。
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EncryptedData {
+    encrypted_data: String,
+    iv: String,
+}
+
+impl EncryptedData {
+    pub fn encrypted_data(&self) -> &str {
+        &self.encrypted_data
+    }
 
-#[derive(Serialize, Deserialize, Clone)]
+    pub fn iv(&self) -> &str {
+        &self.iv
+    }
+}
+
+/// 登录凭证
+///
+/// 序列化时默认不包含 session_key，需要持久化完整凭证时使用 [`Credential::expose`]。
+#[derive(Debug, Deserialize, Clone)]
 pub struct Credential {
     open_id: String,
-    session_key: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    session_key: SecretString,
     union_id: Option<String>,
 }
 
+impl Serialize for Credential {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("Credential", 2)?;
+        state.serialize_field("open_id", &self.open_id)?;
+        if let Some(union_id) = &self.union_id {
+            state.serialize_field("union_id", union_id)?;
+        }
+        state.end()
+    }
+}
+
+/// 包含 session_key 明文的凭证视图
+///
+/// 由 [`Credential::expose`] 创建，仅用于需要持久化完整凭证的场景，
+/// 序列化结果可以通过 [`Credential`] 的 `Deserialize` 还原。
+#[derive(Serialize)]
+pub struct ExposedCredential<'a> {
+    open_id: &'a str,
+    session_key: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    union_id: Option<&'a str>,
+}
+
 impl Credential {
+    /// 使用已保存的 openid 与 session_key 创建凭证
+    ///
+    /// 适用于从自有存储中恢复登录凭证的场景。
+    pub fn new(open_id: &str, session_key: &str, union_id: Option<&str>) -> Self {
+        Credential {
+            open_id: open_id.into(),
+            session_key: session_key.into(),
+            union_id: union_id.map(Into::into),
+        }
+    }
+
     pub fn open_id(&self) -> &str {
         &self.open_id
     }
 
-    pub fn session_key(&self) -> &str {
+    pub fn session_key(&self) -> &SecretString {
         &self.session_key
     }
 
@@ -45,6 +110,26 @@ impl Credential {
         self.union_id.as_deref()
     }
 
+    /// 显式暴露 session_key，用于序列化完整凭证
+    ///
+    /// ```
+    /// use wechat_minapp::Credential;
+    ///
+    /// let credential = Credential::new("open_id", "session_key", None);
+    ///
+    /// let json = serde_json::to_string(&credential.expose()).unwrap();
+    /// let restored: Credential = serde_json::from_str(&json).unwrap();
+    ///
+    /// assert_eq!(restored.session_key().expose_secret(), "session_key");
+    /// ```
+    pub fn expose(&self) -> ExposedCredential<'_> {
+        ExposedCredential {
+            open_id: &self.open_id,
+            session_key: self.session_key.expose_secret(),
+            union_id: self.union_id.as_deref(),
+        }
+    }
+
     /// 解密用户数据，使用的是 AES-128-CBC 算法，数据采用PKCS#7填充。
     /// https://developers.weixin.qq.com/miniprogram/dev/framework/open-ability/signature.html
     /// ```rust
@@ -73,45 +158,181 @@ impl Credential {
     /// ```
     #[instrument(skip(self, encrypted_data, iv))]
     pub fn decrypt(&self, encrypted_data: &str, iv: &str) -> Result<User> {
-        debug!("encrypted_data: {}", encrypted_data);
-        debug!("iv: {}", iv);
+        let builder = self.decrypt_as::<UserBuilder>(encrypted_data, iv)?;
 
-        let key = STANDARD.decode(self.session_key.as_bytes())?;
+        Ok(builder.build())
+    }
+
+    /// 校验用户信息签名
+    ///
+    /// 前端 `wx.getUserInfo()` 返回的 `signature = sha1(rawData + session_key)`，
+    /// 服务端需要重新计算并比对，确保 `rawData` 未被篡改。
+    /// https://developers.weixin.qq.com/miniprogram/dev/framework/open-ability/signature.html
+    ///
+    /// # 错误
+    ///
+    /// 签名不匹配时返回 [`Error::InvalidSignature`]
+    #[instrument(skip(self, raw_data, signature))]
+    pub fn verify_signature(&self, raw_data: &str, signature: &str) -> Result<()> {
+        let mut hasher = Sha1::new();
+        hasher.update(raw_data.as_bytes());
+        hasher.update(self.session_key.expose_secret().as_bytes());
+        let expected = encode(hasher.finalize());
+
+        let signature = signature.to_ascii_lowercase();
+
+        // 逐字节比较全部内容，避免提前返回泄露匹配长度
+        let matched = expected.len() == signature.len()
+            && expected
+                .bytes()
+                .zip(signature.bytes())
+                .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+                == 0;
+
+        if matched {
+            Ok(())
+        } else {
+            Err(Error::InvalidSignature("rawData 签名不匹配".to_string()))
+        }
+    }
+
+    /// 校验签名并解析用户公开信息
+    ///
+    /// 先通过 [`Credential::verify_signature`] 校验，再将 `rawData` 解析为 [`UserInfo`]。
+    ///
+    /// ```no_run
+    /// # use wechat_minapp::Credential;
+    /// # fn example(credential: Credential, raw_data: &str, signature: &str) -> wechat_minapp::Result<()> {
+    /// let user_info = credential.verify_user_info(raw_data, signature)?;
+    /// println!("昵称: {}", user_info.nickname());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip(self, raw_data, signature))]
+    pub fn verify_user_info(&self, raw_data: &str, signature: &str) -> Result<UserInfo> {
+        self.verify_signature(raw_data, signature)?;
+
+        let builder = serde_json::from_str::<UserInfoBuilder>(raw_data)?;
+
+        Ok(builder.build())
+    }
+
+    /// 解密微信运动步数，对应前端 `wx.getWeRunData()`
+    /// https://developers.weixin.qq.com/miniprogram/dev/api/open-api/werun/wx.getWeRunData.html
+    #[instrument(skip(self, encrypted_data, iv))]
+    pub fn decrypt_we_run(&self, encrypted_data: &str, iv: &str) -> Result<WeRunData> {
+        let builder = self.decrypt_as::<WeRunDataBuilder>(encrypted_data, iv)?;
+
+        Ok(builder.build())
+    }
+
+    /// 解密群聊信息，对应前端 `wx.getShareInfo()` 和 `wx.getGroupEnterInfo()`
+    /// https://developers.weixin.qq.com/miniprogram/dev/api/share/wx.getShareInfo.html
+    #[instrument(skip(self, encrypted_data, iv))]
+    pub fn decrypt_group_info(&self, encrypted_data: &str, iv: &str) -> Result<GroupInfo> {
+        let builder = self.decrypt_as::<GroupInfoBuilder>(encrypted_data, iv)?;
+
+        Ok(builder.build())
+    }
+
+    /// 解密旧版手机号，对应前端 `getPhoneNumber` 返回的 encryptedData
+    ///
+    /// 新版请使用 [`Client::get_contact`]
+    /// https://developers.weixin.qq.com/miniprogram/dev/framework/open-ability/deprecatedGetPhoneNumber.html
+    #[instrument(skip(self, encrypted_data, iv))]
+    pub fn decrypt_contact(&self, encrypted_data: &str, iv: &str) -> Result<Contact> {
+        let builder = self.decrypt_as::<PhoneInner>(encrypted_data, iv)?;
+
+        Ok(builder.build())
+    }
+
+    /// 解密开放数据并反序列化为任意类型
+    ///
+    /// 适用于所有使用相同加密方案的开放数据，如分享群信息（openGId）、微信运动步数、
+    /// 旧版手机号、视频号直播信息等。
+    ///
+    /// ```no_run
+    /// use serde::Deserialize;
+    /// # use wechat_minapp::Credential;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct GroupInfo {
+    ///     #[serde(rename = "openGId")]
+    ///     open_g_id: String,
+    /// }
+    ///
+    /// # fn example(credential: Credential) -> wechat_minapp::Result<()> {
+    /// let group = credential.decrypt_as::<GroupInfo>("encrypted_data", "iv")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip(self, encrypted_data, iv))]
+    pub fn decrypt_as<T: DeserializeOwned>(&self, encrypted_data: &str, iv: &str) -> Result<T> {
+        let buffer = self.decrypt_raw(encrypted_data, iv)?;
+
+        Ok(from_slice::<T>(&buffer)?)
+    }
+
+    /// 解密开放数据，返回解密后的原始字节
+    ///
+    /// session_key 和 iv 解码后必须为 16 字节，否则返回 [`Error::AesInvalidLength`]。
+    ///
+    /// [`Error::AesInvalidLength`]: crate::error::Error::AesInvalidLength
+    #[instrument(skip(self, encrypted_data, iv))]
+    pub fn decrypt_raw(&self, encrypted_data: &str, iv: &str) -> Result<Vec<u8>> {
+        let key = STANDARD.decode(self.session_key.expose_secret().as_bytes())?;
         let iv = STANDARD.decode(iv.as_bytes())?;
-        #[allow(deprecated)]
-        let decryptor = Aes128CbcDec::new(
-            &GenericArray::clone_from_slice(&key),
-            &GenericArray::clone_from_slice(&iv),
-        );
+        let decryptor = Aes128CbcDec::new_from_slices(&key, &iv)?;
 
         let encrypted_data = STANDARD.decode(encrypted_data.as_bytes())?;
 
         let buffer = decryptor.decrypt_padded_vec_mut::<Pkcs7>(&encrypted_data)?;
 
-        let builder = from_slice::<UserBuilder>(&buffer)?;
-
-        debug!("user builder: {:#?}", builder);
+        Ok(buffer)
+    }
 
-        Ok(builder.build())
+    /// 加密开放数据，[`Credential::decrypt_raw`] 的逆操作
+    ///
+    /// 使用 AES-128-CBC 算法和 PKCS#7 填充，主要用于离线构造测试数据。
+    ///
+    /// # 参数
+    ///
+    /// - `data`: 待加密的明文
+    /// - `iv`: 16 字节初始向量，为 `None` 时随机生成
+    #[instrument(skip(self, data, iv))]
+    pub fn encrypt_raw(&self, data: &[u8], iv: Option<&[u8]>) -> Result<EncryptedData> {
+        let key = STANDARD.decode(self.session_key.expose_secret().as_bytes())?;
+        let iv = match iv {
+            Some(iv) => iv.to_vec(),
+            None => {
+                let mut iv = vec![0u8; 16];
+                rand::rng().fill_bytes(&mut iv);
+                iv
+            }
+        };
+
+        let encryptor = Aes128CbcEnc::new_from_slices(&key, &iv)?;
+        let buffer = encryptor.encrypt_padded_vec_mut::<Pkcs7>(data);
+
+        Ok(EncryptedData {
+            encrypted_data: STANDARD.encode(buffer),
+            iv: STANDARD.encode(iv),
+        })
     }
-}
 
-impl std::fmt::Debug for Credential {
-    // 为了安全，不打印 session_key
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("Credential")
-            .field("open_id", &self.open_id)
-            .field("session_key", &"********")
-            .field("union_id", &self.union_id)
-            .finish()
+    /// 将数据序列化为 JSON 后加密，[`Credential::decrypt_as`] 的逆操作
+    pub fn encrypt<T: Serialize>(&self, value: &T, iv: Option<&[u8]>) -> Result<EncryptedData> {
+        let data = serde_json::to_vec(value)?;
+
+        self.encrypt_raw(&data, iv)
     }
 }
 
-#[derive(Deserialize)]
+#[derive(Debug, Deserialize)]
 pub(crate) struct CredentialBuilder {
     #[serde(rename = "openid")]
     open_id: String,
-    session_key: String,
+    session_key: SecretString,
     #[serde(rename = "unionid")]
     union_id: Option<String>,
 }
@@ -126,87 +347,309 @@ impl CredentialBuilder {
     }
 }
 
-impl std::fmt::Debug for CredentialBuilder {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("CredentialBuilder")
-            .field("open_id", &self.open_id)
-            .field("session_key", &"********")
-            .field("union_id", &self.union_id)
-            .finish()
-    }
+/// 加密数据校验结果
+///
+/// 由 [`Client::check_encrypted_data`] 返回。
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EncryptedDataCheck {
+    /// 是否为微信生成的加密数据
+    // 微信接口返回的字段名为 vaild
+    #[serde(rename = "vaild", alias = "valid")]
+    valid: bool,
+    /// 加密数据生成的时间戳（秒）
+    create_time: i64,
 }
 
-type HmacSha256 = Hmac<Sha256>;
+impl EncryptedDataCheck {
+    pub fn is_valid(&self) -> bool {
+        self.valid
+    }
+
+    pub fn create_time(&self) -> i64 {
+        self.create_time
+    }
+}
 
 impl Client {
+    /// 检查加密信息是否由微信生成
+    ///
+    /// 计算 `encrypted_data` 的 SHA-256 摘要并提交给微信校验，
+    /// 仅支持校验最近 3 天内生成的加密数据。
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/user-info/basic-info/checkEncryptedData.html
+    #[instrument(
+        skip(self, encrypted_data),
+        fields(endpoint = constants::CHECK_ENCRYPTED_DATA_END_POINT, status, errcode, rid)
+    )]
+    pub async fn check_encrypted_data(&self, encrypted_data: &str) -> Result<EncryptedDataCheck> {
+        let hash = encode(Sha256::digest(encrypted_data.as_bytes()));
+
+        let mut query = HashMap::new();
+        let mut body = HashMap::new();
+
+        query.insert("access_token", self.token().await?);
+        body.insert("encrypted_msg_hash", hash);
+
+        self.guarded(constants::CHECK_ENCRYPTED_DATA_END_POINT, async {
+            let response = self
+                .request()
+                .post(self.url(constants::CHECK_ENCRYPTED_DATA_END_POINT))
+                .query(&query)
+                .json(&body)
+                .send()
+                .await?;
+
+            redact::response(&response);
+
+            if response.status().is_success() {
+                let response = response.json::<Response<EncryptedDataCheck>>().await?;
+
+                let check = response.extract()?;
+
+                debug!(valid = check.valid, "encrypted data checked");
+
+                Ok(check)
+            } else {
+                Err(InternalServer(response.text().await?))
+            }
+        })
+        .await
+    }
+
+    /// 校验加密数据来源后再解密
+    ///
+    /// 先通过 [`Client::check_encrypted_data`] 确认数据由微信生成，
+    /// 再通过 [`Credential::decrypt_as`] 解密。
+    ///
+    /// # 错误
+    ///
+    /// 数据并非由微信生成时返回 [`Error::UntrustedEncryptedData`]
+    ///
+    /// ```no_run
+    /// use wechat_minapp::Client;
+    ///
+    /// # async fn example(client: Client) -> wechat_minapp::Result<()> {
+    /// let credential = client.login("code").await?;
+    /// let group: serde_json::Value = client
+    ///     .check_and_decrypt_as(&credential, "encrypted_data", "iv")
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn check_and_decrypt_as<T: DeserializeOwned>(
+        &self,
+        credential: &Credential,
+        encrypted_data: &str,
+        iv: &str,
+    ) -> Result<T> {
+        let check = self.check_encrypted_data(encrypted_data).await?;
+
+        if !check.is_valid() {
+            return Err(Error::UntrustedEncryptedData(
+                "加密数据并非由微信生成".to_string(),
+            ));
+        }
+
+        credential.decrypt_as(encrypted_data, iv)
+    }
+
     /// 检查登录态是否过期
     /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/user-login/checkSessionKey.html
-    #[instrument(skip(self, session_key, open_id))]
+    #[instrument(
+        skip(self, session_key, open_id),
+        fields(
+            endpoint = constants::CHECK_SESSION_KEY_END_POINT,
+            open_id = %redact::id(open_id),
+            status,
+            errcode,
+            rid
+        )
+    )]
     pub async fn check_session_key(&self, session_key: &str, open_id: &str) -> Result<()> {
-        let mut mac = HmacSha256::new_from_slice(session_key.as_bytes())?;
-        mac.update(b"");
-        let hasher = mac.finalize();
-        let signature = encode(hasher.into_bytes());
+        let signature = signature::hmac_sha256(session_key, b"")?;
 
         let mut map = HashMap::new();
 
+        map.insert("access_token", self.token().await?);
         map.insert("openid", open_id.to_string());
         map.insert("signature", signature);
-        map.insert("sig_method", "hmac_sha256".into());
-
-        let response = self
-            .request()
-            .get(constants::CHECK_SESSION_KEY_END_POINT)
-            .query(&map)
-            .send()
-            .await?;
-
-        debug!("response: {:#?}", response);
-
-        if response.status().is_success() {
-            let response = response.json::<Response<()>>().await?;
-
-            response.extract()
-        } else {
-            Err(crate::error::Error::InternalServer(response.text().await?))
-        }
+        map.insert("sig_method", signature::SIG_METHOD.into());
+
+        self.guarded(constants::CHECK_SESSION_KEY_END_POINT, async {
+            let response = self
+                .request()
+                .get(self.url(constants::CHECK_SESSION_KEY_END_POINT))
+                .query(&map)
+                .send()
+                .await?;
+
+            redact::response(&response);
+
+            if response.status().is_success() {
+                let response = response.json::<Status>().await?;
+
+                response.extract()
+            } else {
+                Err(InternalServer(response.text().await?))
+            }
+        })
+        .await
     }
 
     /// 重置用户的 session_key
     /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/user-login/ResetUserSessionKey.html
-    #[instrument(skip(self, open_id))]
+    #[instrument(
+        skip(self, session_key, open_id),
+        fields(
+            endpoint = constants::RESET_SESSION_KEY_END_POINT,
+            open_id = %redact::id(open_id),
+            status,
+            errcode,
+            rid
+        )
+    )]
     pub async fn reset_session_key(&self, session_key: &str, open_id: &str) -> Result<Credential> {
-        let mut mac = HmacSha256::new_from_slice(session_key.as_bytes())?;
-        mac.update(b"");
-        let hasher = mac.finalize();
-        let signature = encode(hasher.into_bytes());
+        let signature = signature::hmac_sha256(session_key, b"")?;
 
         let mut map = HashMap::new();
 
         map.insert("access_token", self.token().await?);
         map.insert("openid", open_id.to_string());
         map.insert("signature", signature);
-        map.insert("sig_method", "hmac_sha256".into());
+        map.insert("sig_method", signature::SIG_METHOD.into());
 
-        let response = self
-            .request()
-            .get(constants::RESET_SESSION_KEY_END_POINT)
-            .query(&map)
-            .send()
-            .await?;
+        self.guarded(constants::RESET_SESSION_KEY_END_POINT, async {
+            let response = self
+                .request()
+                .get(self.url(constants::RESET_SESSION_KEY_END_POINT))
+                .query(&map)
+                .send()
+                .await?;
 
-        debug!("response: {:#?}", response);
+            redact::response(&response);
 
-        if response.status().is_success() {
-            let response = response.json::<Response<CredentialBuilder>>().await?;
+            if response.status().is_success() {
+                let response = response.json::<Response<CredentialBuilder>>().await?;
 
-            let credential = response.extract()?.build();
+                let credential = response.extract()?.build();
 
-            debug!("credential: {:#?}", credential);
+                Ok(credential)
+            } else {
+                Err(InternalServer(response.text().await?))
+            }
+        })
+        .await
+    }
 
-            Ok(credential)
-        } else {
-            Err(InternalServer(response.text().await?))
+    /// 确保凭证中的 session_key 有效
+    ///
+    /// 先通过 [`Client::check_session_key`] 校验，若 session_key 已过期（87007），
+    /// 则通过 [`Client::reset_session_key`] 重置并返回新的凭证；有效时返回原凭证的副本。
+    /// 重置后的凭证保留原有的 UnionID。
+    ///
+    /// 配合 [`SessionManager::ensure_session_key`] 使用时，新凭证会自动写回会话存储。
+    ///
+    /// ```no_run
+    /// use wechat_minapp::{Client, Credential};
+    ///
+    /// # async fn example(client: Client) -> wechat_minapp::Result<()> {
+    /// // 从自有存储中恢复的 openid 与 session_key
+    /// let credential = Credential::new("user_openid", "stored_session_key", None);
+    ///
+    /// let credential = client.ensure_session_key(&credential).await?;
+    /// let user = credential.decrypt("encrypted_data", "iv")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`SessionManager::ensure_session_key`]: crate::session::SessionManager::ensure_session_key
+    #[instrument(skip(self, credential))]
+    pub async fn ensure_session_key(&self, credential: &Credential) -> Result<Credential> {
+        match self
+            .check_session_key(credential.session_key.expose_secret(), &credential.open_id)
+            .await
+        {
+            Ok(()) => Ok(credential.clone()),
+            Err(Error::SessionKeyNotExistedOrExpired(_)) => {
+                debug!("session key expired, resetting");
+
+                let mut fresh = self
+                    .reset_session_key(credential.session_key.expose_secret(), &credential.open_id)
+                    .await?;
+
+                if fresh.union_id.is_none() {
+                    fresh.union_id = credential.union_id.clone();
+                }
+
+                Ok(fresh)
+            }
+            Err(e) => Err(e),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_user_info() {
+        let credential = Credential::new("open_id", "HyVFkGl5F5OQWJZZaNzBBg==", None);
+        let raw_data = r#"{"nickName":"Band","gender":1,"language":"zh_CN","city":"Guangzhou","province":"Guangdong","country":"CN","avatarUrl":"http://wx.qlogo.cn/mmopen/vi_32/1vZvI39NWFQ9XM4LtQpFrQJ1xlgZxx3w7bQxKARol6503Iuswjjn6nIGBiaycAjAtpujxyzYsrztuuICqIM5ibXQ/0"}"#;
+        let signature = "75e81ceda165f4ffa64f4068af58c64b8f54b88c";
+
+        let user_info = credential.verify_user_info(raw_data, signature).unwrap();
+        assert_eq!(user_info.nickname(), "Band");
+        assert_eq!(user_info.language(), Some("zh_CN"));
+
+        assert!(matches!(
+            credential.verify_signature(raw_data, "0000"),
+            Err(Error::InvalidSignature(_))
+        ));
+    }
+
+    #[test]
+    fn test_serialize_without_session_key() {
+        let credential = Credential::new("open_id", "secret_value", Some("union_id"));
+
+        let json = serde_json::to_value(&credential).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "open_id": "open_id", "union_id": "union_id" })
+        );
+        assert!(!format!("{:?}", credential).contains("secret_value"));
+
+        let json = serde_json::to_string(&credential.expose()).unwrap();
+        let restored: Credential = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.session_key().expose_secret(), "secret_value");
+    }
+
+    #[test]
+    fn test_encrypted_data_check() {
+        let json = r#"{ "errcode": 0, "errmsg": "ok", "vaild": true, "create_time": 1629121902 }"#;
+
+        let check = serde_json::from_str::<Response<EncryptedDataCheck>>(json)
+            .unwrap()
+            .extract()
+            .unwrap();
+
+        assert!(check.is_valid());
+        assert_eq!(check.create_time(), 1629121902);
+    }
+
+    #[test]
+    fn test_decrypt_invalid_key_length() {
+        let credential = Credential::new("open_id", "c2hvcnQ=", None);
+        let iv = STANDARD.encode([0u8; 16]);
+
+        let result = credential.decrypt_raw("AAAAAAAAAAAAAAAAAAAAAA==", &iv);
+        assert!(matches!(result, Err(Error::AesInvalidLength(_))));
+    }
+
+    #[test]
+    fn test_decrypt_invalid_iv_length() {
+        let credential = Credential::new("open_id", &STANDARD.encode([0u8; 16]), None);
+
+        let result = credential.decrypt_raw("AAAAAAAAAAAAAAAAAAAAAA==", "c2hvcnQ=");
+        assert!(matches!(result, Err(Error::AesInvalidLength(_))));
+    }
+}
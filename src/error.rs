@@ -29,6 +29,7 @@
 //! - `serde_json::Error` → `Error::SerdeJson`
 //! - `base64::DecodeError` → `Error::Base64Decode`
 //! - `aes::cipher::InvalidLength` → `Error::AesInvalidLength`
+//! - `std::io::Error` → `Error::Io`
 //!
 //! 这使得错误处理更加方便，可以使用 `?` 操作符自动转换。
 
@@ -69,6 +70,7 @@ use strum::Display;
 ///
 /// - `System`: 微信系统繁忙
 /// - `InternalServer`: 内部服务器错误
+/// - `CircuitOpen`: 接口熔断，请求未发送
 ///
 ///
 /// # 序列化
@@ -98,6 +100,10 @@ pub enum Error {
     #[error("invalid code: {0}")]
     InvalidCode(String),
 
+    /// 登录 code 已被使用
+    #[error("code been used: {0}")]
+    CodeBeenUsed(String),
+
     /// 请求参数错误
     #[error("invalid parameter: {0}")]
     InvalidParameter(String),
@@ -142,6 +148,18 @@ pub enum Error {
     #[error("daily request limit exceeded: {0}")]
     DailyRequestLimitExceeded(String),
 
+    /// 小程序码页面路径不合法
+    #[error("invalid path: {0}")]
+    InvalidPath(String),
+
+    /// 小程序码页面不存在或未发布
+    #[error("invalid page: {0}")]
+    InvalidPage(String),
+
+    /// 生成的小程序码总数达到上限
+    #[error("qr code limit reached: {0}")]
+    QrCodeLimitReached(String),
+
     /// API 调用太频繁，请稍候再试
     #[error("rate limit exceeded: {0}")]
     RateLimitExceeded(String),
@@ -202,11 +220,52 @@ pub enum Error {
     #[error("json error: {0}")]
     SerdeJson(#[from] SerdeJsonError),
 
+    /// 开放数据水印校验失败（AppID 不匹配或时间戳超出有效期）
+    #[error("invalid watermark: {0}")]
+    InvalidWatermark(String),
+
+    /// 加密数据并非由微信生成
+    #[error("untrusted encrypted data: {0}")]
+    UntrustedEncryptedData(String),
+
+    /// 文件读写错误
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// 登录态 token 无效（格式错误或签名不匹配）
+    #[error("invalid session token: {0}")]
+    InvalidSessionToken(String),
+
+    /// 登录态会话不存在或已过期
+    #[error("session not found: {0}")]
+    SessionNotFound(String),
+
+    /// 当前客户端模式不支持该操作，如令牌提供者模式下调用 login
+    #[error("unsupported operation: {0}")]
+    UnsupportedOperation(String),
+
+    /// 接口熔断器已打开，请求未发送，参见 [`circuit_breaker`](crate::circuit_breaker)
+    #[error("circuit open: {0}")]
+    CircuitOpen(String),
+
     /// 内部服务器错误
     #[error("internal error: {0}")]
     InternalServer(String),
 }
 
+impl Error {
+    /// 是否为临时错误：超时、连接失败等网络错误、非 2xx 状态码或微信系统繁忙
+    ///
+    /// 响应体解码失败等请求错误说明接口是可达的，不计入熔断。
+    pub(crate) fn is_transient(&self) -> bool {
+        match self {
+            Error::System(_) | Error::InternalServer(_) => true,
+            Error::Reqwest(e) => e.is_timeout() || e.is_connect() || e.is_request(),
+            _ => false,
+        }
+    }
+}
+
 impl From<UnpadError> for Error {
     fn from(error: UnpadError) -> Self {
         Error::Unpad(error)
@@ -222,7 +281,7 @@ impl From<UnpadError> for Error {
 ///
 /// 完整的错误码列表请参考：
 /// [微信官方文档 - 全局返回码说明](https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/#%E5%85%A8%E5%B1%80%E8%BF%94%E5%9B%9E%E7%A0%81%E8%AF%B4%E6%98%8E)
-#[derive(Debug, Deserialize_repr, Display)]
+#[derive(Debug, Deserialize_repr, Display, Clone, Copy)]
 #[repr(i32)]
 pub enum ErrorCode {
     #[strum(serialize = "系统繁忙，此时请开发者稍候再试")]
@@ -241,6 +300,10 @@ pub enum ErrorCode {
     InvalidParameter = 40097,
     #[strum(serialize = "无效的appsecret，请检查appsecret的正确性")]
     InvalidSecret = 40125,
+    #[strum(serialize = "path 不能为空，且长度不能大于 1024 字节")]
+    InvalidPath = 40159,
+    #[strum(serialize = "code 已被使用")]
+    CodeBeenUsed = 40163,
     #[strum(serialize = "将ip添加到ip白名单列表即可")]
     ForbiddenIp = 40164,
     #[strum(serialize = "高风险等级用户，小程序登录拦截 。风险等级详见用户安全解方案")]
@@ -254,12 +317,18 @@ pub enum ErrorCode {
     #[strum(serialize = "缺少 secret 参数")]
     MissingSecret = 41004,
     MissingCode = 41008,
+    #[strum(
+        serialize = "页面路径不正确，根路径前不要填加 /，不能携带参数，且必须是已经发布的小程序存在的页面"
+    )]
+    InvalidPage = 41030,
     #[strum(serialize = "需要 POST 请求")]
     RequiredPostMethod = 43002,
     #[strum(serialize = "调用超过天级别频率限制。可调用clear_quota接口恢复调用额度。")]
     DailyRequestLimitExceeded = 45009,
     #[strum(serialize = "API 调用太频繁，请稍候再试")]
     RateLimitExceeded = 45011,
+    #[strum(serialize = "生成码个数总和到达最大个数限制")]
+    QrCodeLimitReached = 45029,
     #[strum(serialize = "禁止使用 token 接口")]
     ForbiddenToken = 50004,
     #[strum(serialize = "账号已冻结")]
@@ -305,6 +374,8 @@ impl From<(ErrorCode, String)> for Error {
             InvalidCode => Error::InvalidCode(message),
             InvalidParameter => Error::InvalidParameter(message),
             InvalidSecret => Error::InvalidSecret(message),
+            InvalidPath => Error::InvalidPath(message),
+            CodeBeenUsed => Error::CodeBeenUsed(message),
             ForbiddenIp => Error::ForbiddenIp(message),
             CodeBlocked => Error::CodeBlocked(message),
             SecretFrozen => Error::SecretFrozen(message),
@@ -312,9 +383,11 @@ impl From<(ErrorCode, String)> for Error {
             MissingAppId => Error::MissingAppId(message),
             MissingSecret => Error::MissingSecret(message),
             MissingCode => Error::MissingCode(message),
+            InvalidPage => Error::InvalidPage(message),
             RequiredPostMethod => Error::RequiredPostMethod(message),
             DailyRequestLimitExceeded => Error::DailyRequestLimitExceeded(message),
             RateLimitExceeded => Error::RateLimitExceeded(message),
+            QrCodeLimitReached => Error::QrCodeLimitReached(message),
             ForbiddenToken => Error::ForbiddenToken(message),
             AccountFrozen => Error::AccountFrozen(message),
             ThirdPartyToken => Error::ThirdPartyToken(message),
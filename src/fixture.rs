@@ -0,0 +1,256 @@
+//! 开放数据测试夹具
+//!
+//! 该模块用于离线构造与微信格式一致的加密开放数据，方便测试解密相关的接口，
+//! 无需真实的小程序前端和 session_key。
+//!
+//! 生成的数据包含有效的水印（指定的 AppID 和当前时间戳），
+//! 可以直接通过 [`Credential::decrypt`] 等方法解密，并通过 [`Client::verify_watermark`] 校验。
+//!
+//! # 示例
+//!
+//! ```
+//! use wechat_minapp::fixture::Fixture;
+//!
+//! let fixture = Fixture::builder("wx_app_id")
+//!     .user("nickname", "https://example.com/avatar.png")
+//!     .unwrap();
+//!
+//! let credential = fixture.credential("open_id");
+//! let user = credential
+//!     .decrypt(fixture.encrypted_data(), fixture.iv())
+//!     .unwrap();
+//!
+//! assert_eq!(user.nickname(), "nickname");
+//! assert_eq!(user.app_id(), "wx_app_id");
+//! ```
+//!
+//! [`Credential::decrypt`]: crate::Credential::decrypt
+//! [`Client::verify_watermark`]: crate::Client::verify_watermark
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use chrono::Utc;
+use rand::RngCore;
+use serde_json::{Value, json};
+
+use crate::{Credential, Result, error::Error};
+
+/// 加密开放数据夹具
+///
+/// 包含前端会提交的 `encryptedData`、`iv`，以及解密所需的 `session_key`。
+#[derive(Debug, Clone)]
+pub struct Fixture {
+    encrypted_data: String,
+    iv: String,
+    session_key: String,
+}
+
+impl Fixture {
+    /// 创建夹具构建器
+    pub fn builder(app_id: &str) -> FixtureBuilder {
+        FixtureBuilder::new(app_id)
+    }
+
+    pub fn encrypted_data(&self) -> &str {
+        &self.encrypted_data
+    }
+
+    pub fn iv(&self) -> &str {
+        &self.iv
+    }
+
+    pub fn session_key(&self) -> &str {
+        &self.session_key
+    }
+
+    /// 使用夹具的 session_key 创建凭证
+    pub fn credential(&self, open_id: &str) -> Credential {
+        Credential::new(open_id, &self.session_key, None)
+    }
+}
+
+/// 夹具构建器
+///
+/// 未设置的选项使用默认值：随机 session_key、随机 iv、当前时间戳。
+#[derive(Debug, Clone)]
+pub struct FixtureBuilder {
+    app_id: String,
+    session_key: Option<String>,
+    iv: Option<Vec<u8>>,
+    timestamp: Option<u64>,
+}
+
+impl FixtureBuilder {
+    pub fn new(app_id: &str) -> Self {
+        FixtureBuilder {
+            app_id: app_id.into(),
+            session_key: None,
+            iv: None,
+            timestamp: None,
+        }
+    }
+
+    /// 设置 Base64 编码的 session_key
+    pub fn session_key(mut self, session_key: impl Into<String>) -> Self {
+        self.session_key = Some(session_key.into());
+        self
+    }
+
+    /// 设置 16 字节初始向量
+    pub fn iv(mut self, iv: impl Into<Vec<u8>>) -> Self {
+        self.iv = Some(iv.into());
+        self
+    }
+
+    /// 设置水印时间戳（秒），可用于构造过期数据
+    pub fn timestamp(mut self, timestamp: u64) -> Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
+    /// 构造 `wx.getUserInfo()` 的加密数据
+    pub fn user(self, nickname: &str, avatar: &str) -> Result<Fixture> {
+        self.payload(json!({
+            "nickName": nickname,
+            "gender": 0,
+            "language": "zh_CN",
+            "city": "",
+            "province": "",
+            "country": "",
+            "avatarUrl": avatar,
+        }))
+    }
+
+    /// 构造旧版 `getPhoneNumber` 的加密数据
+    pub fn contact(self, pure_phone_number: &str, country_code: &str) -> Result<Fixture> {
+        self.payload(json!({
+            "phoneNumber": format!("+{} {}", country_code, pure_phone_number),
+            "purePhoneNumber": pure_phone_number,
+            "countryCode": country_code,
+        }))
+    }
+
+    /// 构造 `wx.getWeRunData()` 的加密数据
+    ///
+    /// `steps` 为 `(时间戳, 步数)` 列表
+    pub fn we_run(self, steps: &[(u64, u32)]) -> Result<Fixture> {
+        let list = steps
+            .iter()
+            .map(|(timestamp, step)| json!({ "timestamp": timestamp, "step": step }))
+            .collect::<Vec<_>>();
+
+        self.payload(json!({ "stepInfoList": list }))
+    }
+
+    /// 构造 `wx.getShareInfo()` 的加密数据
+    pub fn group_info(self, open_g_id: &str) -> Result<Fixture> {
+        self.payload(json!({ "openGId": open_g_id }))
+    }
+
+    /// 构造任意开放数据，会自动添加 `watermark` 字段
+    ///
+    /// `payload` 必须为 JSON 对象
+    pub fn payload(self, payload: Value) -> Result<Fixture> {
+        let Value::Object(mut map) = payload else {
+            return Err(Error::InvalidParameter(
+                "payload 必须为 JSON 对象".to_string(),
+            ));
+        };
+
+        let timestamp = self
+            .timestamp
+            .unwrap_or_else(|| Utc::now().timestamp() as u64);
+
+        map.insert(
+            "watermark".to_string(),
+            json!({ "appid": self.app_id, "timestamp": timestamp }),
+        );
+
+        let session_key = self.session_key.unwrap_or_else(|| {
+            let mut key = [0u8; 16];
+            rand::rng().fill_bytes(&mut key);
+            STANDARD.encode(key)
+        });
+
+        let credential = Credential::new("", &session_key, None);
+        let encrypted = credential.encrypt(&map, self.iv.as_deref())?;
+
+        Ok(Fixture {
+            encrypted_data: encrypted.encrypted_data().to_string(),
+            iv: encrypted.iv().to_string(),
+            session_key,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_user_fixture() {
+        let fixture = Fixture::builder("APPID")
+            .user("nickname", "avatar")
+            .unwrap();
+
+        let user = fixture
+            .credential("open_id")
+            .decrypt(fixture.encrypted_data(), fixture.iv())
+            .unwrap();
+
+        assert_eq!(user.nickname(), "nickname");
+        assert_eq!(user.app_id(), "APPID");
+    }
+
+    #[test]
+    fn test_fixed_key_and_iv() {
+        let session_key = STANDARD.encode([1u8; 16]);
+
+        let fixture = Fixture::builder("APPID")
+            .session_key(&session_key)
+            .iv([2u8; 16])
+            .timestamp(1477314187)
+            .we_run(&[(1445866601, 100)])
+            .unwrap();
+
+        assert_eq!(fixture.session_key(), session_key);
+        assert_eq!(fixture.iv(), STANDARD.encode([2u8; 16]));
+
+        let data = fixture
+            .credential("open_id")
+            .decrypt_we_run(fixture.encrypted_data(), fixture.iv())
+            .unwrap();
+
+        assert_eq!(data.step_info_list()[0].step(), 100);
+        assert_eq!(data.timestamp(), 1477314187);
+    }
+
+    #[test]
+    fn test_contact_and_group_fixture() {
+        let fixture = Fixture::builder("APPID")
+            .contact("13800000000", "86")
+            .unwrap();
+
+        let contact = fixture
+            .credential("open_id")
+            .decrypt_contact(fixture.encrypted_data(), fixture.iv())
+            .unwrap();
+
+        assert_eq!(contact.phone_number(), "+86 13800000000");
+
+        let fixture = Fixture::builder("APPID").group_info("OPENGID").unwrap();
+
+        let group = fixture
+            .credential("open_id")
+            .decrypt_group_info(fixture.encrypted_data(), fixture.iv())
+            .unwrap();
+
+        assert_eq!(group.open_g_id(), "OPENGID");
+    }
+
+    #[test]
+    fn test_invalid_payload() {
+        let result = Fixture::builder("APPID").payload(json!([1, 2, 3]));
+
+        assert!(matches!(result, Err(Error::InvalidParameter(_))));
+    }
+}
@@ -1,7 +1,7 @@
 //! `wechat_minapp` - 微信小程序服务端 API 封装库
-//! 
+//!
 //! 该版本不再添加新功能，请使用 [wechat-minapp](https://crates.io/crates/wechat-minapp)
-//! 
+//!
 //! 这是一个为微信小程序服务端 API 提供的 Rust 封装库，旨在简化与微信小程序后端的交互。
 //! 提供了诸如用户登录、内容安全检测、小程序码生成等常用功能的易用接口。
 //!
@@ -20,14 +20,32 @@
 mod access_token;
 mod client;
 mod credential;
+mod login_cache;
 mod qr_code;
 mod response;
+#[cfg(test)]
+mod test_server;
 
+pub mod circuit_breaker;
+pub mod clock;
+pub mod config;
 pub mod constants;
 pub mod error;
+pub mod fixture;
 pub mod minapp_security;
+pub mod network;
+pub mod redact;
+pub mod secret;
+pub mod session;
+pub mod signature;
+pub mod token_provider;
 pub mod user;
+pub mod user_storage;
 
 pub type Result<T> = std::result::Result<T, error::Error>;
-pub use client::Client;
-pub use qr_code::{MinappEnvVersion, QrCode, QrCodeArgs, Rgb};
+pub use access_token::{TokenInfo, TokenSource};
+pub use client::{Client, ClientBuilder};
+pub use credential::{Credential, EncryptedData, EncryptedDataCheck};
+pub use qr_code::{
+    MinappEnvVersion, QrCode, QrCodeArgs, Rgb, UnlimitedQrCodeArgBuilder, UnlimitedQrCodeArgs,
+};
@@ -0,0 +1,98 @@
+use chrono::{DateTime, Duration, Utc};
+use std::{collections::HashMap, sync::Arc, sync::Mutex};
+use tokio::sync::OnceCell;
+
+use crate::credential::Credential;
+
+/// 登录结果缓存
+///
+/// 同一个 `js_code` 只能换取一次 session，前端重试登录请求时第二次调用会返回 40163。
+/// 在时间窗口内，以 `js_code` 为键共享同一次 `jscode2session` 的结果：
+/// 并发调用等待同一个请求完成，后续调用直接返回缓存的凭证。
+/// 请求失败时不缓存，下一个调用者会重新发起请求。
+#[derive(Debug)]
+pub(crate) struct LoginCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<String, Entry>>,
+}
+
+#[derive(Debug)]
+struct Entry {
+    created_at: DateTime<Utc>,
+    cell: Arc<OnceCell<Credential>>,
+}
+
+impl LoginCache {
+    pub(crate) fn new(ttl: Duration) -> Self {
+        LoginCache {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 获取 `code` 对应的结果单元，同时清理过期条目
+    pub(crate) fn cell(&self, code: &str, now: DateTime<Utc>) -> Arc<OnceCell<Credential>> {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+
+        entries.retain(|_, entry| now.signed_duration_since(entry.created_at) < self.ttl);
+
+        entries
+            .entry(code.to_string())
+            .or_insert_with(|| Entry {
+                created_at: now,
+                cell: Arc::new(OnceCell::new()),
+            })
+            .cell
+            .clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_code_shares_cell() {
+        let cache = LoginCache::new(Duration::seconds(30));
+
+        let a = cache.cell("code", Utc::now());
+        let b = cache.cell("code", Utc::now());
+        let c = cache.cell("other", Utc::now());
+
+        assert!(Arc::ptr_eq(&a, &b));
+        assert!(!Arc::ptr_eq(&a, &c));
+    }
+
+    #[test]
+    fn test_expired_entry() {
+        let cache = LoginCache::new(Duration::zero());
+
+        let a = cache.cell("code", Utc::now());
+        let b = cache.cell("code", Utc::now());
+
+        assert!(!Arc::ptr_eq(&a, &b));
+    }
+
+    #[tokio::test]
+    async fn test_single_flight() {
+        let cache = LoginCache::new(Duration::seconds(30));
+        let credential = Credential::new("open_id", "session_key", None);
+
+        let first = cache
+            .cell("code", Utc::now())
+            .get_or_try_init(|| async { Ok::<_, ()>(credential.clone()) })
+            .await
+            .cloned()
+            .unwrap();
+
+        // 第二次调用不会执行初始化函数
+        let second = cache
+            .cell("code", Utc::now())
+            .get_or_try_init(|| async { Err(()) })
+            .await
+            .cloned()
+            .unwrap();
+
+        assert_eq!(first.open_id(), second.open_id());
+    }
+}
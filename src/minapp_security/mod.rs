@@ -1,15 +1,20 @@
 //! 微信小程序内容安全检测模块
 //!
 //! - [`msg_sec_check`][]: 文本内容安全检测。
+//! - [`get_user_risk_rank`][]: 获取用户安全等级，用于发券、注册等场景的风控决策。
 //!
+//! [`msg_sec_check`]: crate::Client::msg_sec_check
+//! [`get_user_risk_rank`]: crate::Client::get_user_risk_rank
 
 mod msg_sec_check;
+mod user_risk_rank;
 
 use serde::{Deserialize, Serialize};
 use serde_repr::Deserialize_repr;
 use strum::Display;
 
 pub use msg_sec_check::{Args, MsgSecCheckResult, Scene};
+pub use user_risk_rank::{RiskRank, RiskRankArgs, RiskRankArgsBuilder, RiskRankResult, RiskScene};
 
 #[derive(Debug, Deserialize_repr, Display, Serialize, PartialEq, Clone)]
 #[repr(i32)]
@@ -48,11 +48,11 @@
 //! ```
 
 use super::{Label, Suggest};
-use crate::{Result, client::Client, constants, error::Error};
+use crate::{Result, client::Client, constants, error::Error, redact};
 use reqwest::header::{CONTENT_TYPE, HeaderMap, HeaderValue};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use tracing::debug;
+use tracing::{debug, instrument};
 
 /// 内容安全检测场景
 ///
@@ -506,8 +506,17 @@ impl Client {
     /// # API 文档
     ///
     /// [文本安全检测](https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/sec-center/sec-check/msgSecCheck.html)
+    #[instrument(
+        skip(self, args),
+        fields(endpoint = constants::MSG_SEC_CHECK_END_POINT, status, errcode, rid)
+    )]
     pub async fn msg_sec_check(&self, args: &Args) -> Result<MsgSecCheckResult> {
-        debug!("msg_sec_check args: {:?}", &args);
+        debug!(
+            content = %redact::pii(&args.content),
+            openid = %redact::id(&args.openid),
+            scene = args.scene as u32,
+            "msg sec check"
+        );
 
         // 验证参数
         args.validate()?;
@@ -540,36 +549,40 @@ impl Client {
         let mut headers = HeaderMap::new();
         headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
 
-        let response = self
-            .request()
-            .post(constants::MSG_SEC_CHECK_END_POINT)
-            .headers(headers)
-            .query(&query)
-            .json(&body)
-            .send()
-            .await?;
-
-        debug!("msg_sec_check response: {:#?}", response);
-
-        if response.status().is_success() {
-            let response_text = response.text().await?;
-            debug!("msg_sec_check response body: {}", response_text);
-
-            let result: MsgSecCheckResult = serde_json::from_str(&response_text)?;
-
-            if result.is_success() {
-                Ok(result)
+        self.guarded(constants::MSG_SEC_CHECK_END_POINT, async {
+            let response = self
+                .request()
+                .post(self.url(constants::MSG_SEC_CHECK_END_POINT))
+                .headers(headers)
+                .query(&query)
+                .json(&body)
+                .send()
+                .await?;
+
+            redact::response(&response);
+
+            if response.status().is_success() {
+                let response_text = response.text().await?;
+
+                let result: MsgSecCheckResult = serde_json::from_str(&response_text)?;
+
+                if result.is_success() {
+                    Ok(result)
+                } else {
+                    // 微信API返回错误
+                    redact::wechat_error(result.errcode, &result.errmsg);
+
+                    Err(Error::InternalServer(format!(
+                        "微信内容安全检测API错误: {} - {}",
+                        result.errcode, result.errmsg
+                    )))
+                }
             } else {
-                // 微信API返回错误
-                Err(Error::InternalServer(format!(
-                    "微信内容安全检测API错误: {} - {}",
-                    result.errcode, result.errmsg
-                )))
+                // HTTP 请求错误
+                Err(Error::InternalServer(response.text().await?))
             }
-        } else {
-            // HTTP 请求错误
-            Err(Error::InternalServer(response.text().await?))
-        }
+        })
+        .await
     }
 }
 
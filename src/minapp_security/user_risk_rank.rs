@@ -0,0 +1,327 @@
+//! 微信小程序用户安全等级模块
+//!
+//! 该模块提供获取用户安全等级的功能，根据用户的 openid、手机号、IP 等信息，
+//! 返回微信安全中心评估的风险等级，可用于发放优惠券、注册等场景的风控决策。
+//!
+//! # 快速开始
+//!
+//! ```no_run
+//! use wechat_minapp::{Client, minapp_security::{RiskRankArgs, RiskScene}};
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let client = Client::new("app_id", "secret");
+//!
+//!     let args = RiskRankArgs::builder()
+//!         .openid("user_openid")
+//!         .scene(RiskScene::Marketing)
+//!         .client_ip("203.0.113.1")
+//!         .build()?;
+//!
+//!     let result = client.get_user_risk_rank(&args).await?;
+//!
+//!     if result.risk_rank().is_risky() {
+//!         println!("高风险用户，拒绝发券");
+//!     }
+//!
+//!     Ok(())
+//! }
+//! ```
+
+use crate::{Result, client::Client, constants, error::Error, redact, response::Response};
+use serde::{Deserialize, Serialize};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+use std::collections::HashMap;
+use strum::Display;
+use tracing::{debug, instrument};
+
+/// 风控场景
+#[derive(Debug, Serialize_repr, Deserialize_repr, Clone, Copy, PartialEq)]
+#[repr(u8)]
+pub enum RiskScene {
+    /// 注册
+    Register = 0,
+    /// 营销作弊
+    Marketing = 1,
+}
+
+/// 用户风险等级
+///
+/// 数值越大风险越高。
+#[derive(Debug, Serialize_repr, Deserialize_repr, Display, Clone, Copy, PartialEq, PartialOrd)]
+#[repr(u8)]
+pub enum RiskRank {
+    #[strum(serialize = "无风险")]
+    None = 0,
+    #[strum(serialize = "低风险")]
+    Low = 1,
+    #[strum(serialize = "中风险")]
+    Medium = 2,
+    #[strum(serialize = "高风险")]
+    High = 3,
+    #[strum(serialize = "极高风险")]
+    Severe = 4,
+}
+
+impl RiskRank {
+    /// 检查是否为中风险及以上
+    pub fn is_risky(&self) -> bool {
+        *self >= RiskRank::Medium
+    }
+}
+
+/// 获取用户安全等级请求参数
+///
+/// 通过 [`RiskRankArgs::builder()`] 创建，appid 由 [`Client`] 自动填充。
+#[derive(Debug, Serialize, Clone)]
+pub struct RiskRankArgs {
+    /// 用户的 openid
+    pub openid: String,
+    /// 场景值
+    pub scene: RiskScene,
+    /// 用户访问源 IP
+    pub client_ip: String,
+    /// 用户手机号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mobile_no: Option<String>,
+    /// 用户邮箱地址
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email_address: Option<String>,
+    /// 额外补充信息
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extended_info: Option<String>,
+    /// 是否为测试调用，测试调用不计入风控模型
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_test: Option<bool>,
+}
+
+impl RiskRankArgs {
+    /// 创建构建器
+    pub fn builder() -> RiskRankArgsBuilder {
+        RiskRankArgsBuilder::new()
+    }
+}
+
+/// RiskRankArgs 构建器，提供链式调用和验证
+#[derive(Debug, Default)]
+pub struct RiskRankArgsBuilder {
+    openid: Option<String>,
+    scene: Option<RiskScene>,
+    client_ip: Option<String>,
+    mobile_no: Option<String>,
+    email_address: Option<String>,
+    extended_info: Option<String>,
+    is_test: Option<bool>,
+}
+
+impl RiskRankArgsBuilder {
+    /// 创建新的构建器实例
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置用户openid
+    pub fn openid(mut self, openid: impl Into<String>) -> Self {
+        self.openid = Some(openid.into());
+        self
+    }
+
+    /// 设置场景
+    pub fn scene(mut self, scene: RiskScene) -> Self {
+        self.scene = Some(scene);
+        self
+    }
+
+    /// 设置用户访问源 IP
+    pub fn client_ip(mut self, client_ip: impl Into<String>) -> Self {
+        self.client_ip = Some(client_ip.into());
+        self
+    }
+
+    /// 设置用户手机号
+    pub fn mobile_no(mut self, mobile_no: impl Into<String>) -> Self {
+        self.mobile_no = Some(mobile_no.into());
+        self
+    }
+
+    /// 设置用户邮箱地址
+    pub fn email_address(mut self, email_address: impl Into<String>) -> Self {
+        self.email_address = Some(email_address.into());
+        self
+    }
+
+    /// 设置额外补充信息
+    pub fn extended_info(mut self, extended_info: impl Into<String>) -> Self {
+        self.extended_info = Some(extended_info.into());
+        self
+    }
+
+    /// 标记为测试调用
+    pub fn with_is_test(mut self) -> Self {
+        self.is_test = Some(true);
+        self
+    }
+
+    /// 构建 RiskRankArgs，验证必填字段
+    pub fn build(self) -> Result<RiskRankArgs> {
+        let openid = self
+            .openid
+            .ok_or(Error::InvalidParameter("openid 是必填参数".to_string()))?;
+        let scene = self
+            .scene
+            .ok_or(Error::InvalidParameter("scene 是必填参数".to_string()))?;
+        let client_ip = self
+            .client_ip
+            .ok_or(Error::InvalidParameter("client_ip 是必填参数".to_string()))?;
+
+        Ok(RiskRankArgs {
+            openid,
+            scene,
+            client_ip,
+            mobile_no: self.mobile_no,
+            email_address: self.email_address,
+            extended_info: self.extended_info,
+            is_test: self.is_test,
+        })
+    }
+}
+
+/// 获取用户安全等级返回结果
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct RiskRankResult {
+    /// 用户风险等级
+    risk_rank: RiskRank,
+    /// 唯一请求标识，标记单次请求（微信接口字段名为 unoin_id）
+    #[serde(rename = "unoin_id")]
+    unoin_id: i64,
+}
+
+impl RiskRankResult {
+    pub fn risk_rank(&self) -> RiskRank {
+        self.risk_rank
+    }
+
+    pub fn unoin_id(&self) -> i64 {
+        self.unoin_id
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct RiskRankBody<'a> {
+    appid: &'a str,
+    #[serde(flatten)]
+    args: &'a RiskRankArgs,
+}
+
+impl Client {
+    /// 获取用户安全等级
+    ///
+    /// 根据用户的 openid、IP、手机号等信息评估用户的风险等级。
+    ///
+    /// # 参数
+    ///
+    /// - `args`: 获取用户安全等级请求参数
+    ///
+    /// # 返回
+    ///
+    /// 成功返回 `Ok(RiskRankResult)`，包含风险等级
+    ///
+    /// # API 文档
+    ///
+    /// [获取用户安全等级](https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/sec-center/safety-control-capability/getUserRiskRank.html)
+    #[instrument(
+        skip(self, args),
+        fields(endpoint = constants::USER_RISK_RANK_END_POINT, status, errcode, rid)
+    )]
+    pub async fn get_user_risk_rank(&self, args: &RiskRankArgs) -> Result<RiskRankResult> {
+        debug!(
+            openid = %redact::id(&args.openid),
+            scene = args.scene as u8,
+            client_ip = %redact::pii(&args.client_ip),
+            "get user risk rank"
+        );
+
+        let mut query = HashMap::new();
+
+        query.insert("access_token", self.token().await?);
+
+        let body = RiskRankBody {
+            appid: self.app_id(),
+            args,
+        };
+
+        self.guarded(constants::USER_RISK_RANK_END_POINT, async {
+            let response = self
+                .request()
+                .post(self.url(constants::USER_RISK_RANK_END_POINT))
+                .query(&query)
+                .json(&body)
+                .send()
+                .await?;
+
+            redact::response(&response);
+
+            if response.status().is_success() {
+                let response = response.json::<Response<RiskRankResult>>().await?;
+
+                response.extract()
+            } else {
+                Err(Error::InternalServer(response.text().await?))
+            }
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_risk_rank_args_builder() {
+        let args = RiskRankArgs::builder()
+            .openid("test_openid")
+            .scene(RiskScene::Register)
+            .client_ip("127.0.0.1")
+            .mobile_no("13800000000")
+            .build()
+            .unwrap();
+
+        let body = serde_json::to_value(RiskRankBody {
+            appid: "APPID",
+            args: &args,
+        })
+        .unwrap();
+
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "appid": "APPID",
+                "openid": "test_openid",
+                "scene": 0,
+                "client_ip": "127.0.0.1",
+                "mobile_no": "13800000000",
+            })
+        );
+
+        let result = RiskRankArgs::builder()
+            .openid("test_openid")
+            .scene(RiskScene::Marketing)
+            .build();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_risk_rank_result() {
+        let json = r#"{ "errcode": 0, "errmsg": "getuserriskrank succ", "risk_rank": 3, "unoin_id": 123456 }"#;
+
+        let result = serde_json::from_str::<Response<RiskRankResult>>(json)
+            .unwrap()
+            .extract()
+            .unwrap();
+
+        assert_eq!(result.risk_rank(), RiskRank::High);
+        assert!(result.risk_rank().is_risky());
+        assert_eq!(result.unoin_id(), 123456);
+    }
+}
@@ -0,0 +1,432 @@
+//! 微信小程序网络诊断模块
+//!
+//! 该模块封装了微信官方的网络检测接口，并提供一个综合诊断报告，
+//! 用于排查生产环境中最常见的配置问题（如 IP 白名单未配置导致的 40164 错误）。
+//!
+//! # 主要功能
+//!
+//! - 网络检测：检查 DNS 解析结果和到各运营商的连通性
+//! - 获取微信 API 服务器 IP 列表
+//! - 综合诊断：AppID/AppSecret 有效性、IP 白名单、DNS 与运营商连通性
+//!
+//! # 快速开始
+//!
+//! ```no_run
+//! use wechat_minapp::Client;
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let client = Client::new("app_id", "secret");
+//!
+//!     let report = client.diagnose().await;
+//!
+//!     if !report.is_healthy() {
+//!         println!("诊断结果: {}", serde_json::to_string_pretty(&report)?);
+//!     }
+//!
+//!     Ok(())
+//! }
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::{debug, instrument};
+
+use crate::{
+    Result,
+    client::Client,
+    constants,
+    error::Error::{self, InternalServer},
+    redact,
+    response::Response,
+};
+
+/// 网络检测动作
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum CheckAction {
+    /// 同时进行域名解析和 ping 检测
+    #[serde(rename = "all")]
+    All,
+    /// 仅进行域名解析
+    #[serde(rename = "dns")]
+    Dns,
+    /// 仅进行 ping 检测
+    #[serde(rename = "ping")]
+    Ping,
+}
+
+/// 运营商
+///
+/// 用于指定网络检测的出口运营商，以及解析检测结果中的运营商字段。
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum Operator {
+    /// 根据 IP 自动选择运营商
+    #[serde(rename = "DEFAULT")]
+    Default,
+    /// 电信
+    #[serde(rename = "CHINANET")]
+    Chinanet,
+    /// 联通
+    #[serde(rename = "UNICOM")]
+    Unicom,
+    /// 腾讯自建
+    #[serde(rename = "CAP")]
+    Cap,
+}
+
+/// 域名解析结果
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DnsResult {
+    /// 解析出来的 IP
+    pub ip: String,
+    /// IP 对应的运营商
+    pub real_operator: String,
+}
+
+/// ping 检测结果
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PingResult {
+    /// ping 的 IP
+    pub ip: String,
+    /// ping 源头的运营商
+    pub from_operator: String,
+    /// 丢包率，如 `"0%"`
+    pub package_loss: String,
+    /// 耗时，如 `"23.079ms"`
+    pub time: String,
+}
+
+impl PingResult {
+    /// 检查该 IP 是否可达（丢包率不为 100%）
+    pub fn is_reachable(&self) -> bool {
+        self.package_loss.trim() != "100%"
+    }
+}
+
+/// 网络检测结果
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CallbackCheck {
+    /// 域名解析结果
+    pub dns: Vec<DnsResult>,
+    /// ping 检测结果
+    pub ping: Vec<PingResult>,
+}
+
+impl CallbackCheck {
+    /// 检查是否至少有一个 IP 可达
+    pub fn is_reachable(&self) -> bool {
+        self.ping.iter().any(PingResult::is_reachable)
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct CallbackCheckArgs {
+    action: CheckAction,
+    check_operator: Operator,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiDomainIp {
+    ip_list: Vec<String>,
+}
+
+/// 单项诊断状态
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(tag = "status", content = "reason", rename_all = "snake_case")]
+pub enum CheckStatus {
+    /// 检查通过
+    Passed,
+    /// 检查失败，附带失败原因
+    Failed(String),
+    /// 由于前置检查失败而跳过
+    Skipped,
+}
+
+impl CheckStatus {
+    /// 检查是否通过
+    pub fn is_passed(&self) -> bool {
+        matches!(self, CheckStatus::Passed)
+    }
+
+    fn failed(error: &Error) -> Self {
+        CheckStatus::Failed(error.to_string())
+    }
+}
+
+/// 综合诊断报告
+///
+/// 由 [`Client::diagnose`] 生成，可以直接序列化后展示在运维页面上。
+///
+/// # 字段说明
+///
+/// - `credential`: AppID/AppSecret 是否有效（能否成功获取 access_token）
+/// - `ip_whitelist`: 当前服务器出口 IP 是否在白名单中
+/// - `api_domain_ip`: 能否获取微信 API 服务器 IP 列表
+/// - `reachability`: DNS 解析与运营商连通性
+#[derive(Debug, Serialize, Clone)]
+pub struct DiagnosisReport {
+    /// AppID/AppSecret 有效性
+    pub credential: CheckStatus,
+    /// IP 白名单状态
+    pub ip_whitelist: CheckStatus,
+    /// 获取微信 API 服务器 IP 状态
+    pub api_domain_ip: CheckStatus,
+    /// DNS 与运营商连通性
+    pub reachability: CheckStatus,
+    /// 微信 API 服务器 IP 列表
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub api_ips: Vec<String>,
+    /// 网络检测详细结果
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub callback_check: Option<CallbackCheck>,
+}
+
+impl DiagnosisReport {
+    /// 检查是否所有诊断项均通过
+    pub fn is_healthy(&self) -> bool {
+        self.credential.is_passed()
+            && self.ip_whitelist.is_passed()
+            && self.api_domain_ip.is_passed()
+            && self.reachability.is_passed()
+    }
+}
+
+impl Client {
+    /// 网络检测
+    ///
+    /// 检查微信服务器到开发者服务器之间的 DNS 解析和连通性。
+    ///
+    /// # 参数
+    ///
+    /// - `action`: 检测动作
+    /// - `operator`: 指定平台从某个运营商进行检测
+    ///
+    /// # API 文档
+    ///
+    /// [网络检测](https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/openApi-mgnt/callbackCheck.html)
+    #[instrument(
+        skip(self),
+        fields(endpoint = constants::CALLBACK_CHECK_END_POINT, status, errcode, rid)
+    )]
+    pub async fn callback_check(
+        &self,
+        action: CheckAction,
+        operator: Operator,
+    ) -> Result<CallbackCheck> {
+        let mut query = HashMap::new();
+
+        query.insert("access_token", self.token().await?);
+
+        let body = CallbackCheckArgs {
+            action,
+            check_operator: operator,
+        };
+
+        self.guarded(constants::CALLBACK_CHECK_END_POINT, async {
+            let response = self
+                .request()
+                .post(self.url(constants::CALLBACK_CHECK_END_POINT))
+                .query(&query)
+                .json(&body)
+                .send()
+                .await?;
+
+            redact::response(&response);
+
+            if response.status().is_success() {
+                let response = response.json::<Response<CallbackCheck>>().await?;
+
+                let result = response.extract()?;
+
+                debug!(
+                    dns = result.dns.len(),
+                    ping = result.ping.len(),
+                    reachable = result.is_reachable(),
+                    "callback check finished"
+                );
+
+                Ok(result)
+            } else {
+                Err(InternalServer(response.text().await?))
+            }
+        })
+        .await
+    }
+
+    /// 获取微信 API 服务器 IP
+    ///
+    /// 返回微信 API 接口的 IP 地址列表，可用于配置防火墙出口规则。
+    ///
+    /// # API 文档
+    ///
+    /// [获取微信API服务器IP](https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/openApi-mgnt/getApiDomainIp.html)
+    #[instrument(
+        skip(self),
+        fields(endpoint = constants::API_DOMAIN_IP_END_POINT, status, errcode, rid)
+    )]
+    pub async fn api_domain_ip(&self) -> Result<Vec<String>> {
+        let mut query = HashMap::new();
+
+        query.insert("access_token", self.token().await?);
+
+        self.guarded(constants::API_DOMAIN_IP_END_POINT, async {
+            let response = self
+                .request()
+                .get(self.url(constants::API_DOMAIN_IP_END_POINT))
+                .query(&query)
+                .send()
+                .await?;
+
+            redact::response(&response);
+
+            if response.status().is_success() {
+                let response = response.json::<Response<ApiDomainIp>>().await?;
+
+                Ok(response.extract()?.ip_list)
+            } else {
+                Err(InternalServer(response.text().await?))
+            }
+        })
+        .await
+    }
+
+    /// 综合网络诊断
+    ///
+    /// 依次获取 access_token、微信 API 服务器 IP 并进行网络检测，
+    /// 汇总为 [`DiagnosisReport`]。该方法不会返回错误，所有失败都记录在报告中。
+    ///
+    /// # 诊断逻辑
+    ///
+    /// - 获取 access_token 成功：AppID/AppSecret 有效，IP 白名单已配置
+    /// - 返回 40164：AppID/AppSecret 无法确认，IP 白名单未配置
+    /// - 返回 AppID/AppSecret 相关错误：凭证无效，跳过后续检查
+    /// - 获取 token 成功后，继续检查 API 服务器 IP 与连通性
+    ///
+    /// 凭证与白名单检查总是向微信重新获取 access_token，不使用缓存的令牌，
+    /// 因此可以发现缓存令牌有效期内被重置的 AppSecret 或被移除的白名单 IP。
+    /// 获取成功时新令牌写入缓存（普通接口签发新令牌后旧令牌会失效），失败时保留缓存的令牌。
+    #[instrument(skip(self))]
+    pub async fn diagnose(&self) -> DiagnosisReport {
+        let mut report = DiagnosisReport {
+            credential: CheckStatus::Skipped,
+            ip_whitelist: CheckStatus::Skipped,
+            api_domain_ip: CheckStatus::Skipped,
+            reachability: CheckStatus::Skipped,
+            api_ips: Vec::new(),
+            callback_check: None,
+        };
+
+        match self.fresh_token().await {
+            Ok(_) => {
+                report.credential = CheckStatus::Passed;
+                report.ip_whitelist = CheckStatus::Passed;
+            }
+            Err(e @ Error::ForbiddenIp(_)) => {
+                report.ip_whitelist = CheckStatus::failed(&e);
+                return report;
+            }
+            Err(e) => {
+                report.credential = CheckStatus::failed(&e);
+                return report;
+            }
+        }
+
+        match self.api_domain_ip().await {
+            Ok(ips) => {
+                report.api_domain_ip = CheckStatus::Passed;
+                report.api_ips = ips;
+            }
+            Err(e) => report.api_domain_ip = CheckStatus::failed(&e),
+        }
+
+        match self
+            .callback_check(CheckAction::All, Operator::Default)
+            .await
+        {
+            Ok(check) => {
+                report.reachability = if check.is_reachable() {
+                    CheckStatus::Passed
+                } else {
+                    CheckStatus::Failed("所有 IP 均不可达".to_string())
+                };
+                report.callback_check = Some(check);
+            }
+            Err(e) => report.reachability = CheckStatus::failed(&e),
+        }
+
+        debug!(
+            healthy = report.is_healthy(),
+            api_ips = report.api_ips.len(),
+            "diagnosis finished"
+        );
+
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_callback_check_result() {
+        let json = r#"
+        {
+            "dns": [
+                { "ip": "111.161.64.40", "real_operator": "UNICOM" }
+            ],
+            "ping": [
+                {
+                    "ip": "111.161.64.40",
+                    "from_operator": "UNICOM",
+                    "package_loss": "0%",
+                    "time": "23.079ms"
+                },
+                {
+                    "ip": "111.161.64.48",
+                    "from_operator": "CHINANET",
+                    "package_loss": "100%",
+                    "time": ""
+                }
+            ]
+        }"#;
+
+        let result = serde_json::from_str::<Response<CallbackCheck>>(json)
+            .unwrap()
+            .extract()
+            .unwrap();
+
+        assert_eq!(result.dns.len(), 1);
+        assert!(result.ping[0].is_reachable());
+        assert!(!result.ping[1].is_reachable());
+        assert!(result.is_reachable());
+    }
+
+    #[test]
+    fn test_callback_check_error() {
+        let json = r#"{ "errcode": 40164, "errmsg": "invalid ip" }"#;
+
+        let result = serde_json::from_str::<Response<CallbackCheck>>(json)
+            .unwrap()
+            .extract();
+
+        assert!(matches!(result, Err(Error::ForbiddenIp(_))));
+
+        // 未在 ErrorCode 中定义的错误码不能被解析为空的检测结果
+        let json = r#"{ "errcode": 42001, "errmsg": "access_token expired" }"#;
+
+        assert!(serde_json::from_str::<Response<CallbackCheck>>(json).is_err());
+    }
+
+    #[test]
+    fn test_check_status_serialization() {
+        let passed = serde_json::to_value(CheckStatus::Passed).unwrap();
+        assert_eq!(passed, serde_json::json!({ "status": "passed" }));
+
+        let failed = serde_json::to_value(CheckStatus::Failed("denied".into())).unwrap();
+        assert_eq!(
+            failed,
+            serde_json::json!({ "status": "failed", "reason": "denied" })
+        );
+    }
+}
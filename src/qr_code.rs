@@ -5,6 +5,8 @@
 //! # 主要功能
 //!
 //! - 生成小程序页面小程序码
+//! - 生成不限制数量的小程序码，通过 `scene` 传递参数
+//! - 生成传统的方形小程序二维码
 //! - 支持自定义尺寸、颜色、透明度等参数
 //! - 支持不同环境版本（开发版、体验版、正式版）
 //! - 链式参数构建器模式
@@ -75,6 +77,22 @@
 //!     .unwrap();
 //! ```
 //!
+//! ## 生成不限制数量的小程序码
+//!
+//! [`Client::qr_code`] 每个账号最多生成 10 万个，用户邀请码等场景使用
+//! [`Client::unlimited_qr_code`]，参数通过 `scene` 传递：
+//!
+//! ```no_run
+//! use wechat_minapp::UnlimitedQrCodeArgs;
+//!
+//! let args = UnlimitedQrCodeArgs::builder()
+//!     .scene("invite=1024")
+//!     .page("pages/invite/invite")
+//!     .width(430)
+//!     .build()
+//!     .unwrap();
+//! ```
+//!
 //! ## 生成简单小程序码
 //!
 //! ```no_run
@@ -90,7 +108,7 @@
 //!
 //! 小程序码生成可能遇到以下错误：
 //!
-//! - 参数验证错误（路径为空或过长）
+//! - 参数验证错误（路径为空或过长，scene 为空、过长或包含不支持的字符）
 //! - 认证错误（access_token 无效）
 //! - 网络错误
 //! - 微信 API 返回错误
@@ -100,15 +118,18 @@
 use crate::{
     Client, Result, constants,
     error::Error::{self, InternalServer},
+    redact,
+    response::Response,
 };
 use reqwest::header::{CONTENT_TYPE, HeaderMap, HeaderValue};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use tracing::debug;
+use tracing::{debug, instrument};
 
 /// 二维码图片数据
 ///
-/// 包含生成的二维码图片的二进制数据，通常是 PNG 格式。
+/// 包含生成的二维码图片的二进制数据，格式见 [`QrCode::content_type`]。
+/// 微信返回的 JSON 错误不会作为图片数据返回，而是解析为对应的 [`Error`]。
 ///
 /// # 示例
 ///
@@ -132,6 +153,8 @@ use tracing::debug;
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct QrCode {
     buffer: Vec<u8>,
+    #[serde(default)]
+    content_type: Option<String>,
 }
 
 impl QrCode {
@@ -145,6 +168,11 @@ impl QrCode {
     pub fn buffer(&self) -> &Vec<u8> {
         &self.buffer
     }
+
+    /// 微信返回的图片类型，如 `image/jpeg`，响应未携带 `Content-Type` 时为 `None`
+    pub fn content_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
+    }
 }
 
 /// 二维码生成参数
@@ -356,6 +384,278 @@ impl QrCodeArgBuilder {
     }
 }
 
+/// 不限制数量的小程序码参数
+///
+/// 用于 [`Client::unlimited_qr_code`]，通过 [`UnlimitedQrCodeArgs::builder()`] 方法创建。
+#[derive(Debug, Deserialize)]
+pub struct UnlimitedQrCodeArgs {
+    scene: String,
+    page: Option<String>,
+    check_path: Option<bool>,
+    width: Option<i16>,
+    auto_color: Option<bool>,
+    line_color: Option<Rgb>,
+    is_hyaline: Option<bool>,
+    env_version: Option<MinappEnvVersion>,
+}
+
+/// 不限制数量的小程序码参数构建器
+///
+/// # 示例
+///
+/// ```
+/// use wechat_minapp::{UnlimitedQrCodeArgs, MinappEnvVersion};
+///
+/// let args = UnlimitedQrCodeArgs::builder()
+///     .scene("a=1&b=2")
+///     .page("pages/index/index")
+///     .check_path(false)
+///     .env_version(MinappEnvVersion::Trial)
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Default, Deserialize)]
+pub struct UnlimitedQrCodeArgBuilder {
+    scene: Option<String>,
+    page: Option<String>,
+    check_path: Option<bool>,
+    width: Option<i16>,
+    auto_color: Option<bool>,
+    line_color: Option<Rgb>,
+    is_hyaline: Option<bool>,
+    env_version: Option<MinappEnvVersion>,
+}
+
+/// `getwxacodeunlimit` 请求体
+#[derive(Debug, Serialize)]
+struct UnlimitedQrCodeBody {
+    scene: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    page: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    check_path: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    width: Option<i16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    auto_color: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line_color: Option<Rgb>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    is_hyaline: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    env_version: Option<String>,
+}
+
+impl UnlimitedQrCodeArgs {
+    pub fn builder() -> UnlimitedQrCodeArgBuilder {
+        UnlimitedQrCodeArgBuilder::new()
+    }
+
+    pub fn scene(&self) -> String {
+        self.scene.clone()
+    }
+
+    pub fn page(&self) -> Option<String> {
+        self.page.clone()
+    }
+
+    pub fn check_path(&self) -> Option<bool> {
+        self.check_path
+    }
+
+    pub fn width(&self) -> Option<i16> {
+        self.width
+    }
+
+    pub fn auto_color(&self) -> Option<bool> {
+        self.auto_color
+    }
+
+    pub fn line_color(&self) -> Option<Rgb> {
+        self.line_color.clone()
+    }
+
+    pub fn is_hyaline(&self) -> Option<bool> {
+        self.is_hyaline
+    }
+
+    pub fn env_version(&self) -> Option<MinappEnvVersion> {
+        self.env_version.clone()
+    }
+
+    fn into_body(self) -> UnlimitedQrCodeBody {
+        UnlimitedQrCodeBody {
+            scene: self.scene,
+            page: self.page,
+            check_path: self.check_path,
+            width: self.width,
+            auto_color: self.auto_color,
+            line_color: self.line_color,
+            is_hyaline: self.is_hyaline,
+            env_version: self.env_version.map(Into::into),
+        }
+    }
+}
+
+impl UnlimitedQrCodeArgBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 场景参数，最大 32 个可见字符，
+    /// 只支持数字、大小写英文以及部分特殊字符：`!#$&'()*+,/:;=?@-._~`
+    pub fn scene(mut self, scene: impl Into<String>) -> Self {
+        self.scene = Some(scene.into());
+        self
+    }
+
+    /// 已发布小程序的页面路径，根路径前不要填加 `/`，不填默认跳主页面
+    pub fn page(mut self, page: impl Into<String>) -> Self {
+        self.page = Some(page.into());
+        self
+    }
+
+    /// 是否检查页面是否存在，默认检查；为 `false` 时允许未发布的页面
+    pub fn check_path(mut self, check_path: bool) -> Self {
+        self.check_path = Some(check_path);
+        self
+    }
+
+    /// 小程序码宽度，单位 px，最小 280px，最大 1280px，默认 430px
+    pub fn width(mut self, width: i16) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    pub fn with_auto_color(mut self) -> Self {
+        self.auto_color = Some(true);
+        self
+    }
+
+    pub fn line_color(mut self, color: Rgb) -> Self {
+        self.line_color = Some(color);
+        self
+    }
+
+    pub fn with_is_hyaline(mut self) -> Self {
+        self.is_hyaline = Some(true);
+        self
+    }
+
+    pub fn env_version(mut self, version: MinappEnvVersion) -> Self {
+        self.env_version = Some(version);
+        self
+    }
+
+    pub fn build(self) -> Result<UnlimitedQrCodeArgs> {
+        let scene = self.scene.map_or_else(
+            || Err(Error::InvalidParameter("scene 不能为空".to_string())),
+            |v| {
+                if v.is_empty() {
+                    return Err(Error::InvalidParameter("scene 不能为空".to_string()));
+                }
+
+                if v.chars().count() > 32 {
+                    return Err(Error::InvalidParameter(
+                        "scene 最大长度 32 个字符".to_string(),
+                    ));
+                }
+
+                if let Some(c) = v.chars().find(|c| !is_scene_char(*c)) {
+                    return Err(Error::InvalidParameter(format!(
+                        "scene 包含不支持的字符: {:?}",
+                        c
+                    )));
+                }
+
+                Ok(v)
+            },
+        )?;
+
+        if let Some(page) = &self.page
+            && page.starts_with('/')
+        {
+            return Err(Error::InvalidParameter("page 不能以 / 开头".to_string()));
+        }
+
+        if let Some(width) = self.width
+            && !(280..=1280).contains(&width)
+        {
+            return Err(Error::InvalidParameter(
+                "二维码宽度范围为 280px 至 1280px".to_string(),
+            ));
+        }
+
+        Ok(UnlimitedQrCodeArgs {
+            scene,
+            page: self.page,
+            check_path: self.check_path,
+            width: self.width,
+            auto_color: self.auto_color,
+            line_color: self.line_color,
+            is_hyaline: self.is_hyaline,
+            env_version: self.env_version,
+        })
+    }
+}
+
+/// `createwxaqrcode` 请求体
+#[derive(Debug, Serialize)]
+struct CreateQrCodeBody<'a> {
+    path: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    width: Option<i16>,
+}
+
+/// 解析小程序码接口的响应体
+///
+/// 微信在参数错误、超出调用限制等情况下以 HTTP 200 返回 JSON 错误，
+/// 而不是图片数据。`Content-Type` 为 JSON 或文本，或响应体以 `{` 开头时，
+/// 按 [`Response`] 解析为对应的错误；未知的错误码返回 [`Error::InternalServer`]。
+fn parse_qr_code(content_type: Option<String>, buffer: Vec<u8>) -> Result<QrCode> {
+    if is_json(content_type.as_deref(), &buffer) {
+        let text = String::from_utf8_lossy(&buffer).into_owned();
+
+        if let Ok(response) = serde_json::from_str::<Response<()>>(&text) {
+            response.extract()?;
+        }
+
+        // JSON 响应体不是图片数据：未知错误码或 errcode 为 0 时同样返回错误
+
+        if let Ok(error) = serde_json::from_str::<UnknownError>(&text) {
+            redact::wechat_error(error.errcode, &error.errmsg);
+        }
+
+        return Err(InternalServer(text));
+    }
+
+    Ok(QrCode {
+        buffer,
+        content_type,
+    })
+}
+
+/// 未在 [`ErrorCode`](crate::error::ErrorCode) 中定义的微信错误
+#[derive(Debug, Deserialize)]
+struct UnknownError {
+    errcode: i32,
+    errmsg: String,
+}
+
+fn is_json(content_type: Option<&str>, buffer: &[u8]) -> bool {
+    let declared = content_type.is_some_and(|v| {
+        let v = v.to_ascii_lowercase();
+        v.contains("json") || v.starts_with("text/")
+    });
+
+    declared || buffer.trim_ascii_start().first() == Some(&b'{')
+}
+
+/// scene 支持的字符：数字、大小写英文以及 `!#$&'()*+,/:;=?@-._~`
+fn is_scene_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "!#$&'()*+,/:;=?@-._~".contains(c)
+}
+
 impl Client {
     /// 生成小程序二维码
     ///
@@ -393,13 +693,15 @@ impl Client {
     /// - 认证错误（access_token 无效）
     /// - 微信 API 返回错误
     /// - 参数序列化错误
+    #[instrument(
+        skip(self, args),
+        fields(endpoint = constants::QR_CODE_ENDPOINT, status, errcode, rid)
+    )]
     pub async fn qr_code(&self, args: QrCodeArgs) -> Result<QrCode> {
-        debug!("get qr code args {:?}", &args);
+        debug!(path = %redact::pii(&args.path), width = ?args.width, "get qr code");
 
-        let mut query = HashMap::new();
         let mut body = HashMap::new();
 
-        query.insert("access_token", self.token().await?);
         body.insert("path", args.path);
 
         if let Some(width) = args.width {
@@ -423,29 +725,334 @@ impl Client {
             body.insert("env_version", env_version.into());
         }
 
+        self.fetch_qr_code(constants::QR_CODE_ENDPOINT, &body).await
+    }
+
+    /// 生成不限制数量的小程序码
+    ///
+    /// 适用于用户邀请码等需要大量生成的场景，参数通过 `scene` 传递，
+    /// 小程序页面通过 `onLoad` 的 `options.scene` 获取。
+    ///
+    /// # 参数
+    ///
+    /// - `args`: 小程序码生成参数
+    ///
+    /// # 返回
+    ///
+    /// 成功返回 `Ok(QrCode)`，失败返回错误信息。
+    ///
+    /// # 示例
+    ///
+    /// ```no_run
+    /// use wechat_minapp::{Client, UnlimitedQrCodeArgs};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("app_id", "secret");
+    /// let args = UnlimitedQrCodeArgs::builder()
+    ///     .scene("invite=1024")
+    ///     .page("pages/invite/invite")
+    ///     .build()?;
+    ///
+    /// let qr_code = client.unlimited_qr_code(args).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # 错误
+    ///
+    /// - 网络错误
+    /// - 认证错误（access_token 无效）
+    /// - 微信 API 返回错误
+    ///
+    /// # API 文档
+    ///
+    /// [获取不限制的小程序码](https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/qrcode-link/qr-code/getUnlimitedQRCode.html)
+    #[instrument(
+        skip(self, args),
+        fields(endpoint = constants::UNLIMITED_QR_CODE_ENDPOINT, status, errcode, rid)
+    )]
+    pub async fn unlimited_qr_code(&self, args: UnlimitedQrCodeArgs) -> Result<QrCode> {
+        debug!(
+            scene = %redact::pii(&args.scene),
+            page = ?args.page,
+            width = ?args.width,
+            "get unlimited qr code"
+        );
+
+        self.fetch_qr_code(constants::UNLIMITED_QR_CODE_ENDPOINT, &args.into_body())
+            .await
+    }
+
+    /// 生成方形小程序二维码
+    ///
+    /// 调用 `cgi-bin/wxaapp/createwxaqrcode` 接口，生成传统的方形二维码，
+    /// 适用于需要标准二维码的印刷物料。与 [`Client::qr_code`] 共享每个账号 10 万个的数量限制。
+    ///
+    /// # 参数
+    ///
+    /// - `path`: 小程序页面路径，可以带参数，最大长度 128 字节
+    /// - `width`: 二维码宽度，单位 px，最小 280px，最大 1280px，默认 430px
+    ///
+    /// # 返回
+    ///
+    /// 成功返回 `Ok(QrCode)`，失败返回错误信息。
+    ///
+    /// # 示例
+    ///
+    /// ```no_run
+    /// use wechat_minapp::Client;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("app_id", "secret");
+    ///
+    /// let qr_code = client.create_qr_code("pages/index/index?id=1", 430).await?;
+    /// // std::fs::write("qrcode.png", qr_code.buffer())?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # 错误
+    ///
+    /// - 参数验证错误（路径为空或过长，宽度超出范围）
+    /// - 网络错误
+    /// - 认证错误（access_token 无效）
+    /// - 微信 API 返回错误
+    ///
+    /// # API 文档
+    ///
+    /// [获取小程序二维码](https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/qrcode-link/qr-code/createQRCode.html)
+    #[instrument(
+        skip(self, path, width),
+        fields(endpoint = constants::CREATE_QR_CODE_ENDPOINT, status, errcode, rid)
+    )]
+    pub async fn create_qr_code(
+        &self,
+        path: &str,
+        width: impl Into<Option<i16>>,
+    ) -> Result<QrCode> {
+        let width = width.into();
+
+        if path.is_empty() {
+            return Err(Error::InvalidParameter(
+                "小程序页面路径不能为空".to_string(),
+            ));
+        }
+
+        if path.len() > 128 {
+            return Err(Error::InvalidParameter(
+                "页面路径最大长度 128 个字节".to_string(),
+            ));
+        }
+
+        if let Some(width) = width
+            && !(280..=1280).contains(&width)
+        {
+            return Err(Error::InvalidParameter(
+                "二维码宽度范围为 280px 至 1280px".to_string(),
+            ));
+        }
+
+        debug!(path = %redact::pii(path), width = ?width, "create qr code");
+
+        let body = CreateQrCodeBody { path, width };
+
+        self.fetch_qr_code(constants::CREATE_QR_CODE_ENDPOINT, &body)
+            .await
+    }
+
+    async fn fetch_qr_code<B: Serialize>(
+        &self,
+        endpoint: &'static str,
+        body: &B,
+    ) -> Result<QrCode> {
+        let mut query = HashMap::new();
+
+        query.insert("access_token", self.token().await?);
+
         let mut headers = HeaderMap::new();
         headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
         headers.insert("encoding", HeaderValue::from_static("null"));
 
-        let response = self
-            .request()
-            .post(constants::QR_CODE_ENDPOINT)
-            .headers(headers)
-            .query(&query)
-            .json(&body)
-            .send()
-            .await?;
+        self.guarded(endpoint, async {
+            let response = self
+                .request()
+                .post(self.url(endpoint))
+                .headers(headers)
+                .query(&query)
+                .json(body)
+                .send()
+                .await?;
 
-        debug!("response: {:#?}", response);
+            redact::response(&response);
 
-        if response.status().is_success() {
-            let response = response.bytes().await?;
+            if response.status().is_success() {
+                let content_type = response
+                    .headers()
+                    .get(CONTENT_TYPE)
+                    .and_then(|v| v.to_str().ok())
+                    .map(str::to_string);
 
-            Ok(QrCode {
-                buffer: response.to_vec(),
-            })
-        } else {
-            Err(InternalServer(response.text().await?))
+                let response = response.bytes().await?;
+
+                parse_qr_code(content_type, response.to_vec())
+            } else {
+                Err(InternalServer(response.text().await?))
+            }
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unlimited_args_scene() {
+        let args = UnlimitedQrCodeArgs::builder()
+            .scene("a=1&b=2~!#$'()*+,/:;?@-._")
+            .build()
+            .unwrap();
+        assert_eq!(args.scene(), "a=1&b=2~!#$'()*+,/:;?@-._");
+
+        for scene in ["", "a%20b", "中文", &"a".repeat(33)] {
+            let result = UnlimitedQrCodeArgs::builder().scene(scene).build();
+            assert!(
+                matches!(result, Err(Error::InvalidParameter(_))),
+                "{}",
+                scene
+            );
+        }
+
+        assert!(UnlimitedQrCodeArgs::builder().build().is_err());
+        assert!(
+            UnlimitedQrCodeArgs::builder()
+                .scene("a=1")
+                .page("/pages/index/index")
+                .build()
+                .is_err()
+        );
+
+        for width in [279, 1281] {
+            let result = UnlimitedQrCodeArgs::builder()
+                .scene("a=1")
+                .width(width)
+                .build();
+            assert!(matches!(result, Err(Error::InvalidParameter(_))));
         }
+        assert!(
+            UnlimitedQrCodeArgs::builder()
+                .scene("a=1")
+                .width(1280)
+                .build()
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_parse_qr_code() {
+        let png = b"\x89PNG\r\n\x1a\n".to_vec();
+        let qr_code = parse_qr_code(Some("image/png".into()), png.clone()).unwrap();
+        assert_eq!(qr_code.buffer(), &png);
+        assert_eq!(qr_code.content_type(), Some("image/png"));
+
+        let json = br#"{"errcode":45009,"errmsg":"reach max api daily quota limit"}"#;
+        assert!(matches!(
+            parse_qr_code(
+                Some("application/json; encoding=utf-8".into()),
+                json.to_vec()
+            ),
+            Err(Error::DailyRequestLimitExceeded(_))
+        ));
+
+        // 未携带 Content-Type 时按响应体识别
+        let json = b" {\"errcode\":41030,\"errmsg\":\"invalid page rid: 1\"}";
+        assert!(matches!(
+            parse_qr_code(None, json.to_vec()),
+            Err(Error::InvalidPage(_))
+        ));
+
+        // 未知错误码
+        let json = br#"{"errcode":85096,"errmsg":"not allow include scancode_time field"}"#;
+        assert!(matches!(
+            parse_qr_code(Some("image/jpeg".into()), json.to_vec()),
+            Err(Error::InternalServer(_))
+        ));
+
+        let text = b"errcode: -1".to_vec();
+        assert!(matches!(
+            parse_qr_code(Some("text/plain".into()), text),
+            Err(Error::InternalServer(_))
+        ));
+    }
+
+    #[test]
+    fn test_create_qr_code_json_error() {
+        for json in [
+            r#"{"errcode":85096,"errmsg":"not allow include scancode_time field"}"#,
+            r#"{"errcode":0,"errmsg":"ok"}"#,
+            r#"{}"#,
+        ] {
+            assert!(
+                matches!(
+                    parse_qr_code(None, json.as_bytes().to_vec()),
+                    Err(Error::InternalServer(_))
+                ),
+                "{}",
+                json
+            );
+        }
+    }
+
+    #[test]
+    fn test_create_qr_code_body() {
+        let body = CreateQrCodeBody {
+            path: "pages/index/index",
+            width: None,
+        };
+        assert_eq!(
+            serde_json::to_string(&body).unwrap(),
+            r#"{"path":"pages/index/index"}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_qr_code_validation() {
+        let client = Client::new("app_id", "secret");
+
+        for (path, width) in [
+            ("", None),
+            (&*"a".repeat(129), None),
+            ("pages/index", Some(100)),
+        ] {
+            assert!(matches!(
+                client.create_qr_code(path, width).await,
+                Err(Error::InvalidParameter(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_unlimited_body() {
+        let body = UnlimitedQrCodeArgs::builder()
+            .scene("a=1")
+            .page("pages/index/index")
+            .check_path(false)
+            .line_color(Rgb::new(0, 0, 0))
+            .env_version(MinappEnvVersion::Trial)
+            .build()
+            .unwrap()
+            .into_body();
+
+        assert_eq!(
+            serde_json::to_value(&body).unwrap(),
+            serde_json::json!({
+                "scene": "a=1",
+                "page": "pages/index/index",
+                "check_path": false,
+                "line_color": { "r": 0, "g": 0, "b": 0 },
+                "env_version": "trial"
+            })
+        );
     }
 }
@@ -0,0 +1,189 @@
+//! 日志脱敏模块
+//!
+//! 本库的 `tracing` 日志中可能出现用户隐私数据（手机号 code、待检测文本等）
+//! 和用户标识（openid、unionid 等），输出前统一经过该模块的脱敏策略处理。
+//!
+//! # 脱敏策略
+//!
+//! - [`Redaction::None`] - 原样输出，仅建议在本地调试时使用
+//! - [`Redaction::Mask`] - 默认策略，隐私数据与用户标识均只保留首尾少量字符
+//! - [`Redaction::Hash`] - 隐私数据打码，用户标识输出 SHA-256 摘要，便于关联同一用户的日志
+//!
+//! # 日志字段
+//!
+//! 调用微信接口的方法会创建包含以下字段的 span：
+//!
+//! - `endpoint`: 接口地址
+//! - `status`: HTTP 状态码
+//! - `errcode`: 微信返回的错误码
+//! - `rid`: 微信返回的请求 ID，可用于向微信官方反馈问题
+//!
+//! # 示例
+//!
+//! ```
+//! use wechat_minapp::redact::{self, Redaction};
+//!
+//! redact::set_redaction(Redaction::Hash);
+//!
+//! assert_eq!(redact::pii("13800000000").to_string(), "13****00");
+//! assert!(redact::id("open_id").to_string().starts_with("sha256:"));
+//! ```
+
+use hex::encode;
+use sha2::{Digest, Sha256};
+use std::sync::atomic::{AtomicU8, Ordering};
+use tracing::{Span, debug};
+
+/// 日志脱敏策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(u8)]
+pub enum Redaction {
+    /// 不脱敏
+    None = 0,
+    /// 打码
+    #[default]
+    Mask = 1,
+    /// 隐私数据打码，用户标识输出摘要
+    Hash = 2,
+}
+
+static REDACTION: AtomicU8 = AtomicU8::new(Redaction::Mask as u8);
+
+/// 设置全局日志脱敏策略
+pub fn set_redaction(redaction: Redaction) {
+    REDACTION.store(redaction as u8, Ordering::Relaxed);
+}
+
+/// 获取当前的全局日志脱敏策略
+pub fn redaction() -> Redaction {
+    match REDACTION.load(Ordering::Relaxed) {
+        0 => Redaction::None,
+        2 => Redaction::Hash,
+        _ => Redaction::Mask,
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Kind {
+    Pii,
+    Id,
+}
+
+/// 按当前脱敏策略格式化的值
+///
+/// 仅在日志实际输出时才进行脱敏计算。
+#[derive(Clone, Copy)]
+pub struct Redacted<'a> {
+    value: &'a str,
+    kind: Kind,
+}
+
+/// 包装用户隐私数据，如手机号 code、待检测文本、二维码路径参数等
+pub fn pii(value: &str) -> Redacted<'_> {
+    Redacted {
+        value,
+        kind: Kind::Pii,
+    }
+}
+
+/// 包装用户标识，如 openid、unionid 等
+pub fn id(value: &str) -> Redacted<'_> {
+    Redacted {
+        value,
+        kind: Kind::Id,
+    }
+}
+
+impl std::fmt::Display for Redacted<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (redaction(), self.kind) {
+            (Redaction::None, _) => f.write_str(self.value),
+            (Redaction::Hash, Kind::Id) => {
+                let digest = encode(Sha256::digest(self.value.as_bytes()));
+                write!(f, "sha256:{}", &digest[..16])
+            }
+            _ => mask(self.value, f),
+        }
+    }
+}
+
+impl std::fmt::Debug for Redacted<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "\"{}\"", self)
+    }
+}
+
+// 长度不超过 6 的值全部打码，否则保留首尾各 2 个字符
+fn mask(value: &str, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let chars = value.chars().collect::<Vec<_>>();
+
+    if chars.len() <= 6 {
+        return f.write_str("****");
+    }
+
+    let head = chars[..2].iter().collect::<String>();
+    let tail = chars[chars.len() - 2..].iter().collect::<String>();
+
+    write!(f, "{}****{}", head, tail)
+}
+
+/// 记录 HTTP 响应状态，替代直接输出整个 `reqwest::Response`
+pub(crate) fn response(response: &reqwest::Response) {
+    let status = response.status().as_u16();
+
+    Span::current().record("status", status);
+
+    debug!(status, "response received");
+}
+
+/// 在当前 span 中记录微信返回的错误码和请求 ID
+pub(crate) fn wechat_error(errcode: i32, errmsg: &str) {
+    let span = Span::current();
+
+    span.record("errcode", errcode);
+
+    if let Some(rid) = rid(errmsg) {
+        span.record("rid", rid);
+    }
+}
+
+/// 从 errmsg 中提取请求 ID，如 `invalid code, rid: 6f1c2b3a-...`
+fn rid(errmsg: &str) -> Option<&str> {
+    errmsg
+        .rsplit_once("rid:")
+        .map(|(_, rid)| rid.trim())
+        .filter(|rid| !rid.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redaction() {
+        // 全局策略会被并行测试共享，集中在一个测试中验证
+        assert_eq!(redaction(), Redaction::Mask);
+        assert_eq!(pii("13800000000").to_string(), "13****00");
+        assert_eq!(pii("123456").to_string(), "****");
+        assert_eq!(id("oUpF8uMuAJO_M2pxb1Q9zNjWeS6o").to_string(), "oU****6o");
+
+        set_redaction(Redaction::Hash);
+        assert_eq!(pii("13800000000").to_string(), "13****00");
+        assert_eq!(id("open_id").to_string().len(), "sha256:".len() + 16);
+        assert_eq!(id("open_id").to_string(), id("open_id").to_string());
+
+        set_redaction(Redaction::None);
+        assert_eq!(pii("13800000000").to_string(), "13800000000");
+
+        set_redaction(Redaction::Mask);
+    }
+
+    #[test]
+    fn test_rid() {
+        assert_eq!(
+            rid("invalid code, rid: 6f1c2b3a-1a2b3c4d-5e6f7a8b"),
+            Some("6f1c2b3a-1a2b3c4d-5e6f7a8b")
+        );
+        assert_eq!(rid("ok"), None);
+    }
+}
@@ -1,22 +1,29 @@
 use serde::Deserialize;
 use tracing::{Level, event};
 
-use crate::{Result, error::ErrorCode};
+use crate::{
+    Result,
+    error::{Error, ErrorCode},
+    redact,
+};
 
 /// 微信小程序返回的数据结构
+///
+/// 先尝试按错误结构解析：成功响应中的 `errcode` 为 0，不属于 [`ErrorCode`]，
+/// 因此不会被误判为错误；而字段均可缺省的成功结构也不会吞掉错误响应。
 #[derive(Debug, Deserialize)]
 #[serde(untagged)]
 pub(crate) enum Response<T> {
-    Success {
-        #[serde(flatten)]
-        data: T,
-    },
     Error {
         #[serde(rename = "errcode")]
         code: ErrorCode,
         #[serde(rename = "errmsg")]
         message: String,
     },
+    Success {
+        #[serde(flatten)]
+        data: T,
+    },
 }
 
 impl<T> Response<T> {
@@ -25,6 +32,8 @@ impl<T> Response<T> {
         match self {
             Self::Success { data } => Ok(data),
             Self::Error { code, message } => {
+                redact::wechat_error(code as i32, &message);
+
                 event!(
                     Level::ERROR,
                     "微信小程序返回错误: code={}, message={}",
@@ -37,3 +46,40 @@ impl<T> Response<T> {
         }
     }
 }
+
+/// 只返回 `errcode`/`errmsg` 的接口响应
+///
+/// [`Response<()>`] 会把未在 [`ErrorCode`] 中定义的错误码解析为成功，
+/// 这类接口需要严格检查 `errcode` 是否为 0。
+#[derive(Debug, Deserialize)]
+pub(crate) struct Status {
+    errcode: i32,
+    #[serde(default)]
+    errmsg: String,
+}
+
+impl Status {
+    /// `errcode` 为 0 时返回 `Ok(())`，否则转换为对应的错误，
+    /// 未知错误码返回 [`Error::InternalServer`]
+    pub(crate) fn extract(self) -> Result<()> {
+        if self.errcode == 0 {
+            return Ok(());
+        }
+
+        match serde_json::from_value::<ErrorCode>(self.errcode.into()) {
+            Ok(code) => Response::<()>::Error {
+                code,
+                message: self.errmsg,
+            }
+            .extract(),
+            Err(_) => {
+                redact::wechat_error(self.errcode, &self.errmsg);
+
+                Err(Error::InternalServer(format!(
+                    "errcode: {}, errmsg: {}",
+                    self.errcode, self.errmsg
+                )))
+            }
+        }
+    }
+}
@@ -0,0 +1,102 @@
+//! 敏感信息封装模块
+//!
+//! AppSecret、session_key、access_token 等敏感信息使用 [`SecretString`] 保存：
+//!
+//! - `Debug` 与 `Display` 输出均为 `********`，避免误打印到日志
+//! - 离开作用域时自动清零内存
+//! - 未实现 `Serialize`，需要通过 [`SecretString::expose_secret`] 显式取出明文
+//!
+//! # 示例
+//!
+//! ```
+//! use wechat_minapp::secret::SecretString;
+//!
+//! let secret = SecretString::new("app_secret");
+//!
+//! assert_eq!(format!("{:?}", secret), "********");
+//! assert_eq!(secret.expose_secret(), "app_secret");
+//! ```
+
+use serde::{Deserialize, Deserializer};
+use zeroize::Zeroize;
+
+/// 敏感字符串
+///
+/// 离开作用域时清零，调试输出时脱敏。
+#[derive(Clone, Default)]
+pub struct SecretString(String);
+
+impl SecretString {
+    pub fn new(secret: impl Into<String>) -> Self {
+        SecretString(secret.into())
+    }
+
+    /// 显式取出明文
+    pub fn expose_secret(&self) -> &str {
+        &self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Drop for SecretString {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl std::fmt::Debug for SecretString {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("********")
+    }
+}
+
+impl std::fmt::Display for SecretString {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("********")
+    }
+}
+
+impl From<String> for SecretString {
+    fn from(secret: String) -> Self {
+        SecretString(secret)
+    }
+}
+
+impl From<&str> for SecretString {
+    fn from(secret: &str) -> Self {
+        SecretString(secret.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for SecretString {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        String::deserialize(deserializer).map(SecretString)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redacted() {
+        let secret = SecretString::from("session_key");
+
+        assert_eq!(format!("{:?}", secret), "********");
+        assert_eq!(secret.to_string(), "********");
+        assert_eq!(secret.expose_secret(), "session_key");
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let secret: SecretString = serde_json::from_str("\"token\"").unwrap();
+
+        assert_eq!(secret.expose_secret(), "token");
+    }
+}
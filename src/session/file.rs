@@ -0,0 +1,95 @@
+use std::{
+    fs,
+    io::{ErrorKind, Write},
+    path::{Path, PathBuf},
+};
+
+use super::{Session, SessionStore};
+use crate::{Result, error::Error};
+
+/// 文件会话存储
+///
+/// 每个会话保存为目录下的一个 JSON 文件，文件名为会话 ID。
+/// 适用于单机多进程部署。文件中包含 session_key，在 Unix 系统上新建的目录权限为 `0700`，
+/// 会话文件权限为 `0600`。
+#[derive(Debug, Clone)]
+pub struct FileSessionStore {
+    dir: PathBuf,
+}
+
+impl FileSessionStore {
+    /// 创建文件会话存储，目录不存在时自动创建
+    pub fn new(dir: impl AsRef<Path>) -> Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        create_dir(&dir)?;
+
+        Ok(FileSessionStore { dir })
+    }
+
+    fn path(&self, id: &str) -> Result<PathBuf> {
+        // 会话 ID 作为文件名，只允许字母和数字，避免路径穿越
+        if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(Error::InvalidSessionToken("会话 ID 格式错误".to_string()));
+        }
+
+        Ok(self.dir.join(format!("{}.json", id)))
+    }
+}
+
+#[cfg(unix)]
+fn create_dir(dir: &Path) -> std::io::Result<()> {
+    use std::os::unix::fs::DirBuilderExt;
+
+    fs::DirBuilder::new()
+        .recursive(true)
+        .mode(0o700)
+        .create(dir)
+}
+
+#[cfg(not(unix))]
+fn create_dir(dir: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(dir)
+}
+
+/// 以仅所有者可读写的权限打开文件，用于写入临时文件
+fn open_private(path: &Path) -> std::io::Result<fs::File> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+
+        options.mode(0o600);
+    }
+
+    options.open(path)
+}
+
+impl SessionStore for FileSessionStore {
+    fn insert(&self, id: &str, session: Session) -> Result<()> {
+        let path = self.path(id)?;
+        let tmp = path.with_extension("json.tmp");
+
+        open_private(&tmp)?.write_all(&serde_json::to_vec(&session)?)?;
+        fs::rename(tmp, path)?;
+
+        Ok(())
+    }
+
+    fn get(&self, id: &str) -> Result<Option<Session>> {
+        match fs::read(self.path(id)?) {
+            Ok(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn remove(&self, id: &str) -> Result<()> {
+        match fs::remove_file(self.path(id)?) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
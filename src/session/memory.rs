@@ -0,0 +1,56 @@
+use chrono::{DateTime, Utc};
+use std::{collections::HashMap, sync::Mutex};
+
+use super::{Session, SessionStore};
+use crate::Result;
+
+/// 内存会话存储
+///
+/// 适用于单实例部署或测试环境，进程重启后会话丢失。
+#[derive(Debug, Default)]
+pub struct MemorySessionStore {
+    sessions: Mutex<HashMap<String, Session>>,
+}
+
+impl MemorySessionStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 清理在 `now` 时已过期的会话
+    pub fn purge_expired(&self, now: DateTime<Utc>) {
+        self.sessions
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .retain(|_, session| !session.is_expired(now));
+    }
+}
+
+impl SessionStore for MemorySessionStore {
+    fn insert(&self, id: &str, session: Session) -> Result<()> {
+        self.sessions
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(id.to_string(), session);
+
+        Ok(())
+    }
+
+    fn get(&self, id: &str) -> Result<Option<Session>> {
+        Ok(self
+            .sessions
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(id)
+            .cloned())
+    }
+
+    fn remove(&self, id: &str) -> Result<()> {
+        self.sessions
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(id);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,565 @@
+//! 微信小程序自定义登录态模块
+//!
+//! [`Client::login`] 返回的 [`Credential`] 包含 session_key，不应下发给小程序前端。
+//! 该模块在服务端保存 [`Credential`]，并向前端签发不透明的登录态 token，
+//! 后续解密、校验、重置 session_key 时只需凭 token 即可。
+//!
+//! # 主要功能
+//!
+//! - [`SessionStore`]: 会话存储接口，内置内存实现 [`MemorySessionStore`] 和文件实现 [`FileSessionStore`]
+//! - [`SessionManager`]: 签发 HMAC-SHA256 签名的登录态 token，并提供基于 token 的查询辅助方法
+//! - session_key 过期时自动重置并写回存储，参见 [`SessionManager::ensure_session_key`]
+//!
+//! # 快速开始
+//!
+//! ```no_run
+//! use wechat_minapp::Client;
+//! use wechat_minapp::session::{MemorySessionStore, SessionManager};
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let client = Client::new("app_id", "secret");
+//!     let sessions = SessionManager::new(MemorySessionStore::new(), "signing_key");
+//!
+//!     // 登录并签发登录态 token，将 token 返回给小程序前端
+//!     let credential = client.login("code").await?;
+//!     let token = sessions.issue(credential)?;
+//!
+//!     // 前端携带 token 与加密数据请求解密
+//!     let user = sessions.decrypt(&client, &token, "encrypted_data", "iv").await?;
+//!     println!("昵称: {}", user.nickname());
+//!
+//!     Ok(())
+//! }
+//! ```
+//!
+//! [`Client::login`]: crate::Client::login
+
+mod file;
+mod memory;
+
+use chrono::{DateTime, Duration, Utc};
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use serde::{Deserialize, Serialize, Serializer};
+use sha2::Sha256;
+use std::sync::Arc;
+use tracing::{debug, instrument};
+
+use crate::{
+    Client, Credential, Result,
+    clock::{Clock, SystemClock},
+    error::Error,
+    user::User,
+};
+
+pub use file::FileSessionStore;
+pub use memory::MemorySessionStore;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// 会话
+///
+/// 保存用户的 [`Credential`] 及其过期时间。
+///
+/// 序列化时包含 session_key 明文，以便持久化存储后恢复凭证。
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Session {
+    #[serde(serialize_with = "Session::serialize_credential")]
+    credential: Credential,
+    expired_at: DateTime<Utc>,
+}
+
+impl Session {
+    /// 创建新的会话
+    pub fn new(credential: Credential, expired_at: DateTime<Utc>) -> Self {
+        Session {
+            credential,
+            expired_at,
+        }
+    }
+
+    pub fn credential(&self) -> &Credential {
+        &self.credential
+    }
+
+    fn serialize_credential<S>(
+        credential: &Credential,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        credential.expose().serialize(serializer)
+    }
+
+    pub fn expired_at(&self) -> DateTime<Utc> {
+        self.expired_at
+    }
+
+    /// 检查会话在 `now` 时是否已过期
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expired_at <= now
+    }
+}
+
+/// 会话存储接口
+///
+/// 以会话 ID 为键保存 [`Session`]，可以基于 Redis、数据库等实现自定义存储。
+/// 过期判断由 [`SessionManager`] 负责，存储实现只需原样保存和读取。
+pub trait SessionStore: Send + Sync {
+    /// 保存会话，已存在时覆盖
+    fn insert(&self, id: &str, session: Session) -> Result<()>;
+
+    /// 读取会话，不存在时返回 `None`
+    fn get(&self, id: &str) -> Result<Option<Session>>;
+
+    /// 删除会话，不存在时不报错
+    fn remove(&self, id: &str) -> Result<()>;
+}
+
+/// 登录态管理器
+///
+/// 签发和校验登录态 token。token 格式为 `{session_id}.{signature}`，
+/// 其中 signature 为使用签名密钥对会话 ID 计算的 HMAC-SHA256 十六进制值。
+///
+/// # 示例
+///
+/// ```
+/// use wechat_minapp::session::{MemorySessionStore, SessionManager};
+/// use chrono::Duration;
+///
+/// let sessions = SessionManager::new(MemorySessionStore::new(), "signing_key")
+///     .ttl(Duration::hours(2));
+/// ```
+pub struct SessionManager<S: SessionStore> {
+    store: S,
+    signing_key: Vec<u8>,
+    ttl: Duration,
+    clock: Arc<dyn Clock>,
+}
+
+impl<S: SessionStore> SessionManager<S> {
+    /// 创建登录态管理器
+    ///
+    /// # 参数
+    ///
+    /// - `store`: 会话存储
+    /// - `signing_key`: token 签名密钥，请使用足够长的随机值并妥善保管
+    ///
+    /// 默认会话有效期为 1 天，可通过 [`SessionManager::ttl`] 修改。
+    pub fn new(store: S, signing_key: impl AsRef<[u8]>) -> Self {
+        SessionManager {
+            store,
+            signing_key: signing_key.as_ref().to_vec(),
+            ttl: Duration::days(1),
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// 设置会话有效期
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// 使用自定义时钟，参见 [`clock`](crate::clock) 模块
+    pub fn clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// 保存凭证并签发登录态 token
+    #[instrument(skip(self, credential))]
+    pub fn issue(&self, credential: Credential) -> Result<String> {
+        let mut bytes = [0u8; 32];
+        rand::rng().fill_bytes(&mut bytes);
+        let id = hex::encode(bytes);
+
+        let session = Session::new(credential, self.clock.now() + self.ttl);
+        self.store.insert(&id, session)?;
+
+        let signature = self.sign(&id)?;
+
+        debug!("session issued");
+
+        Ok(format!("{}.{}", id, signature))
+    }
+
+    /// 校验 token 签名，返回会话 ID
+    pub fn verify(&self, token: &str) -> Result<String> {
+        let (id, signature) = token
+            .split_once('.')
+            .ok_or_else(|| Error::InvalidSessionToken("token 格式错误".to_string()))?;
+
+        let signature = hex::decode(signature)
+            .map_err(|_| Error::InvalidSessionToken("token 签名格式错误".to_string()))?;
+
+        let mut mac = HmacSha256::new_from_slice(&self.signing_key)?;
+        mac.update(id.as_bytes());
+        mac.verify_slice(&signature)
+            .map_err(|_| Error::InvalidSessionToken("token 签名不匹配".to_string()))?;
+
+        Ok(id.to_string())
+    }
+
+    /// 根据 token 获取会话
+    ///
+    /// 会话不存在或已过期时返回 [`Error::SessionNotFound`]，过期会话会被删除。
+    pub fn session(&self, token: &str) -> Result<Session> {
+        let id = self.verify(token)?;
+
+        match self.store.get(&id)? {
+            Some(session) if !session.is_expired(self.clock.now()) => Ok(session),
+            Some(_) => {
+                self.store.remove(&id)?;
+                Err(Error::SessionNotFound("会话已过期".to_string()))
+            }
+            None => Err(Error::SessionNotFound("会话不存在".to_string())),
+        }
+    }
+
+    /// 根据 token 获取凭证
+    pub fn credential(&self, token: &str) -> Result<Credential> {
+        Ok(self.session(token)?.credential)
+    }
+
+    /// 注销 token 对应的会话
+    pub fn revoke(&self, token: &str) -> Result<()> {
+        let id = self.verify(token)?;
+
+        self.store.remove(&id)
+    }
+
+    /// 使用 token 对应的 session_key 解密用户数据
+    ///
+    /// 解密前先通过 [`SessionManager::ensure_session_key`] 确认 session_key 有效，
+    /// 过期时重置并写回会话存储。参见 [`Credential::decrypt`]
+    pub async fn decrypt(
+        &self,
+        client: &Client,
+        token: &str,
+        encrypted_data: &str,
+        iv: &str,
+    ) -> Result<User> {
+        self.ensure_session_key(client, token)
+            .await?
+            .decrypt(encrypted_data, iv)
+    }
+
+    /// 检查 token 对应的 session_key 是否有效
+    ///
+    /// 参见 [`Client::check_session_key`]
+    pub async fn check_session_key(&self, client: &Client, token: &str) -> Result<()> {
+        let credential = self.credential(token)?;
+
+        client
+            .check_session_key(
+                credential.session_key().expose_secret(),
+                credential.open_id(),
+            )
+            .await
+    }
+
+    /// 重置 token 对应的 session_key，并更新会话存储
+    ///
+    /// 会话的过期时间保持不变。参见 [`Client::reset_session_key`]
+    pub async fn reset_session_key(&self, client: &Client, token: &str) -> Result<()> {
+        let id = self.verify(token)?;
+        let session = self.session(token)?;
+        let credential = session.credential();
+
+        let credential = client
+            .reset_session_key(
+                credential.session_key().expose_secret(),
+                credential.open_id(),
+            )
+            .await?;
+
+        self.store
+            .insert(&id, Session::new(credential, session.expired_at))
+    }
+
+    /// 确保 token 对应的 session_key 有效，必要时重置并更新会话存储
+    ///
+    /// 返回有效的凭证，可直接用于解密。参见 [`Client::ensure_session_key`]
+    pub async fn ensure_session_key(&self, client: &Client, token: &str) -> Result<Credential> {
+        let id = self.verify(token)?;
+        let session = self.session(token)?;
+
+        let credential = client.ensure_session_key(session.credential()).await?;
+
+        if credential.session_key().expose_secret()
+            != session.credential().session_key().expose_secret()
+        {
+            self.store
+                .insert(&id, Session::new(credential.clone(), session.expired_at))?;
+        }
+
+        Ok(credential)
+    }
+
+    fn sign(&self, id: &str) -> Result<String> {
+        let mut mac = HmacSha256::new_from_slice(&self.signing_key)?;
+        mac.update(id.as_bytes());
+
+        Ok(hex::encode(mac.finalize().into_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{clock::ManualClock, fixture::Fixture, test_server};
+
+    fn credential() -> Credential {
+        Credential::new("open_id", "c2Vzc2lvbl9rZXlfMTIzNA==", None)
+    }
+
+    #[test]
+    fn test_issue_and_lookup() {
+        let sessions = SessionManager::new(MemorySessionStore::new(), "key");
+
+        let token = sessions.issue(credential()).unwrap();
+        let found = sessions.credential(&token).unwrap();
+
+        assert_eq!(found.open_id(), "open_id");
+
+        sessions.revoke(&token).unwrap();
+        assert!(matches!(
+            sessions.credential(&token),
+            Err(Error::SessionNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_tampered_token() {
+        let sessions = SessionManager::new(MemorySessionStore::new(), "key");
+        let other = SessionManager::new(MemorySessionStore::new(), "other_key");
+
+        let token = sessions.issue(credential()).unwrap();
+
+        assert!(matches!(
+            other.verify(&token),
+            Err(Error::InvalidSessionToken(_))
+        ));
+        assert!(matches!(
+            sessions.verify("no_signature"),
+            Err(Error::InvalidSessionToken(_))
+        ));
+    }
+
+    #[test]
+    fn test_file_store() {
+        let dir =
+            std::env::temp_dir().join(format!("wechat-minapp-session-{}", std::process::id()));
+        let sessions = SessionManager::new(FileSessionStore::new(&dir).unwrap(), "key");
+
+        let token = sessions.issue(credential()).unwrap();
+        let found = sessions.credential(&token).unwrap();
+
+        assert_eq!(found.open_id(), "open_id");
+        assert_eq!(
+            found.session_key().expose_secret(),
+            credential().session_key().expose_secret()
+        );
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            let id = token.split_once('.').unwrap().0;
+            let mode = |path: &std::path::Path| {
+                std::fs::metadata(path).unwrap().permissions().mode() & 0o777
+            };
+            assert_eq!(mode(&dir), 0o700);
+            assert_eq!(mode(&dir.join(format!("{}.json", id))), 0o600);
+        }
+
+        sessions.revoke(&token).unwrap();
+        assert!(sessions.credential(&token).is_err());
+        assert!(sessions.store().get("../escape").is_err());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_session_expires_with_clock() {
+        let clock = ManualClock::new(Utc::now());
+        let store = MemorySessionStore::new();
+        let sessions = SessionManager::new(MemorySessionStore::new(), "key")
+            .ttl(Duration::hours(2))
+            .clock(clock.clone());
+
+        let token = sessions.issue(credential()).unwrap();
+
+        clock.advance(Duration::hours(2) - Duration::seconds(1));
+        assert!(sessions.credential(&token).is_ok());
+
+        clock.advance(Duration::seconds(1));
+        assert!(matches!(
+            sessions.credential(&token),
+            Err(Error::SessionNotFound(_))
+        ));
+
+        store
+            .insert("id", Session::new(credential(), clock.now()))
+            .unwrap();
+        store.purge_expired(clock.now() - Duration::seconds(1));
+        assert!(store.get("id").unwrap().is_some());
+        store.purge_expired(clock.now());
+        assert!(store.get("id").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_expired_session() {
+        let sessions =
+            SessionManager::new(MemorySessionStore::new(), "key").ttl(Duration::seconds(-1));
+
+        let token = sessions.issue(credential()).unwrap();
+
+        assert!(matches!(
+            sessions.credential(&token),
+            Err(Error::SessionNotFound(_))
+        ));
+    }
+
+    /// 模拟 session_key 校验与重置接口，`check` 为 checksession 的响应
+    async fn client(check: &'static str, session_key: String) -> Client {
+        let base_url = test_server::serve(move |request| {
+            if request.contains("/cgi-bin/stable_token") {
+                r#"{"access_token":"token","expires_in":7200}"#.to_string()
+            } else if request.contains("/wxa/checksession") {
+                check.to_string()
+            } else if request.contains("/wxa/resetusersessionkey") {
+                format!(
+                    r#"{{"openid":"open_id","session_key":"{}","errcode":0,"errmsg":"ok"}}"#,
+                    session_key
+                )
+            } else {
+                r#"{"errcode":-1,"errmsg":"system error"}"#.to_string()
+            }
+        })
+        .await;
+
+        Client::builder("wx_app_id", "secret")
+            .base_url(&base_url)
+            .build()
+    }
+
+    #[tokio::test]
+    async fn test_ensure_session_key_valid() {
+        let fixture = Fixture::builder("wx_app_id")
+            .user("nickname", "https://example.com/avatar.png")
+            .unwrap();
+        let client = client(r#"{"errcode":0,"errmsg":"ok"}"#, "unused".into()).await;
+        let sessions = SessionManager::new(MemorySessionStore::new(), "key");
+
+        let token = sessions.issue(fixture.credential("open_id")).unwrap();
+
+        let user = sessions
+            .decrypt(&client, &token, fixture.encrypted_data(), fixture.iv())
+            .await
+            .unwrap();
+        assert_eq!(user.nickname(), "nickname");
+    }
+
+    #[tokio::test]
+    async fn test_ensure_session_key_reset() {
+        let fixture = Fixture::builder("wx_app_id")
+            .user("nickname", "https://example.com/avatar.png")
+            .unwrap();
+        let client = client(
+            r#"{"errcode":87007,"errmsg":"session_key is not existed or expired"}"#,
+            fixture.session_key().to_string(),
+        )
+        .await;
+        let sessions = SessionManager::new(MemorySessionStore::new(), "key");
+
+        // 会话中保存的是已过期的 session_key
+        let token = sessions
+            .issue(Credential::new("open_id", "stale_key", Some("union_id")))
+            .unwrap();
+
+        let user = sessions
+            .decrypt(&client, &token, fixture.encrypted_data(), fixture.iv())
+            .await
+            .unwrap();
+        assert_eq!(user.nickname(), "nickname");
+
+        // 新的 session_key 已写回存储，UnionID 保持不变
+        let stored = sessions.credential(&token).unwrap();
+        assert_eq!(stored.session_key().expose_secret(), fixture.session_key());
+        assert_eq!(stored.union_id(), Some("union_id"));
+    }
+
+    #[tokio::test]
+    async fn test_ensure_session_key_error() {
+        let client = client(
+            r#"{"errcode":87009,"errmsg":"invalid signature"}"#,
+            "unused".into(),
+        )
+        .await;
+        let sessions = SessionManager::new(MemorySessionStore::new(), "key");
+
+        let token = sessions.issue(credential()).unwrap();
+
+        assert!(matches!(
+            sessions.ensure_session_key(&client, &token).await,
+            Err(Error::InvalidSignature(_))
+        ));
+        assert!(matches!(
+            sessions
+                .decrypt(&client, &token, "encrypted_data", "iv")
+                .await,
+            Err(Error::InvalidSignature(_))
+        ));
+
+        // 校验失败时不修改存储的凭证
+        assert_eq!(
+            sessions
+                .credential(&token)
+                .unwrap()
+                .session_key()
+                .expose_secret(),
+            credential().session_key().expose_secret()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ensure_session_key_unknown_errcode() {
+        let fixture = Fixture::builder("wx_app_id")
+            .user("nickname", "https://example.com/avatar.png")
+            .unwrap();
+        let client = client(
+            r#"{"errcode":42001,"errmsg":"access_token expired"}"#,
+            fixture.session_key().to_string(),
+        )
+        .await;
+        let sessions = SessionManager::new(MemorySessionStore::new(), "key");
+
+        let token = sessions
+            .issue(Credential::new("open_id", "stale_key", None))
+            .unwrap();
+
+        // 未知错误码不能被当作校验通过，否则会用过期的 session_key 解密
+        assert!(matches!(
+            sessions
+                .decrypt(&client, &token, fixture.encrypted_data(), fixture.iv())
+                .await,
+            Err(Error::InternalServer(_))
+        ));
+        assert_eq!(
+            sessions
+                .credential(&token)
+                .unwrap()
+                .session_key()
+                .expose_secret(),
+            "stale_key"
+        );
+    }
+}
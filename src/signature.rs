@@ -0,0 +1,56 @@
+//! 用户登录态签名模块
+//!
+//! 部分微信接口（如检查登录态、重置 session_key、用户托管数据）要求使用
+//! session_key 对请求内容进行签名，签名方法为 `hmac_sha256`。
+//!
+//! # 签名规则
+//!
+//! - 检查登录态、重置 session_key、获取用户加密密钥：对空字符串签名
+//! - 设置、删除用户托管数据：对 POST 请求体签名
+//!
+//! # 示例
+//!
+//! ```
+//! use wechat_minapp::signature;
+//!
+//! let signature = signature::hmac_sha256("session_key", b"").unwrap();
+//! assert_eq!(signature.len(), 64);
+//! ```
+
+use hex::encode;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::Result;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// 签名方法，作为 `sig_method` 参数传递给微信接口
+pub const SIG_METHOD: &str = "hmac_sha256";
+
+/// 使用 session_key 对消息签名，返回十六进制编码的 HMAC-SHA256 值
+pub fn hmac_sha256(session_key: &str, message: &[u8]) -> Result<String> {
+    let mut mac = HmacSha256::new_from_slice(session_key.as_bytes())?;
+    mac.update(message);
+
+    Ok(encode(mac.finalize().into_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hmac_sha256() {
+        let signature = hmac_sha256(
+            "o0q0otL8aEzpcZL/FT9WsQ==",
+            br#"{"kv_list":[{"key":"1","value":"0"}]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            signature,
+            "e5d7a81858330b061028db572d3ebe89299afd9b7f4be1dd0c0bd315582ea3fe"
+        );
+    }
+}
@@ -0,0 +1,35 @@
+//! 测试用的本地 HTTP 服务，模拟微信接口
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+/// 启动本地服务并返回其地址，可用作 [`ClientBuilder::base_url`]
+///
+/// `handler` 接收请求行（如 `GET /wxa/checksession?... HTTP/1.1`），返回 JSON 响应体。
+///
+/// [`ClientBuilder::base_url`]: crate::ClientBuilder::base_url
+pub(crate) async fn serve<F>(handler: F) -> String
+where
+    F: Fn(&str) -> String + Send + Sync + 'static,
+{
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let mut buf = vec![0; 4096];
+            let n = stream.read(&mut buf).await.unwrap_or(0);
+            let request = String::from_utf8_lossy(&buf[..n]);
+
+            let body = handler(request.lines().next().unwrap_or_default());
+
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            let _ = stream.write_all(response.as_bytes()).await;
+        }
+    });
+
+    format!("http://{}", addr)
+}
@@ -0,0 +1,221 @@
+//! 外部访问令牌模块
+//!
+//! 部分部署要求只有中控服务持有 AppSecret，其他服务从中控服务获取 access_token。
+//! 通过 [`Client::with_token_provider`] 创建的客户端不持有 AppSecret，
+//! 所有需要 access_token 的接口均通过 [`TokenProvider`] 获取令牌，
+//! 仅 [`Client::login`] 等需要 AppSecret 的接口不可用。
+//!
+//! # 内置实现
+//!
+//! - [`HttpTokenProvider`] - 通过 HTTP 从中控服务获取令牌
+//! - [`StaticTokenProvider`] - 使用固定的令牌，适用于测试或由外部进程定期注入
+//!
+//! # 示例
+//!
+//! ```no_run
+//! use wechat_minapp::{Client, token_provider::HttpTokenProvider};
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let provider = HttpTokenProvider::new("http://token-service.internal/token")
+//!         .header("authorization", "Bearer internal_key")?;
+//!
+//!     let client = Client::with_token_provider("app_id", provider);
+//!
+//!     let access_token = client.token().await?;
+//!
+//!     Ok(())
+//! }
+//! ```
+//!
+//! [`Client::with_token_provider`]: crate::Client::with_token_provider
+//! [`Client::login`]: crate::Client::login
+
+use chrono::{DateTime, Duration, Utc};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use serde::Deserialize;
+use std::{future::Future, pin::Pin};
+use tracing::{debug, instrument};
+
+use crate::{
+    Result,
+    access_token::{AccessToken, TokenSource},
+    error::Error,
+    redact,
+    response::Response,
+    secret::SecretString,
+};
+
+/// 异步返回值
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// 外部提供的访问令牌
+///
+/// 与微信 `cgi-bin/token` 接口的返回格式一致。
+#[derive(Debug, Deserialize, Clone)]
+pub struct ProvidedToken {
+    access_token: SecretString,
+    /// 有效期（秒）
+    expires_in: i64,
+}
+
+impl ProvidedToken {
+    pub fn new(access_token: &str, expires_in: i64) -> Self {
+        ProvidedToken {
+            access_token: access_token.into(),
+            expires_in,
+        }
+    }
+
+    pub(crate) fn build(self, now: DateTime<Utc>) -> AccessToken {
+        AccessToken {
+            access_token: self.access_token,
+            expired_at: now + Duration::seconds(self.expires_in),
+            source: TokenSource::External,
+        }
+    }
+}
+
+/// 访问令牌提供者
+///
+/// 令牌过期时由 [`Client`](crate::Client) 调用，并发调用会被合并为一次。
+pub trait TokenProvider: Send + Sync + std::fmt::Debug {
+    /// 获取访问令牌
+    ///
+    /// `force_refresh` 为 `true` 时，提供者应当返回新的令牌而不是缓存的令牌。
+    fn fetch(&self, force_refresh: bool) -> BoxFuture<'_, Result<ProvidedToken>>;
+}
+
+/// 固定令牌提供者
+#[derive(Debug, Clone)]
+pub struct StaticTokenProvider {
+    token: ProvidedToken,
+}
+
+impl StaticTokenProvider {
+    /// 使用固定的令牌，有效期默认 7200 秒
+    pub fn new(access_token: &str) -> Self {
+        StaticTokenProvider {
+            token: ProvidedToken::new(access_token, 7200),
+        }
+    }
+
+    /// 设置令牌有效期（秒）
+    pub fn expires_in(mut self, expires_in: i64) -> Self {
+        self.token.expires_in = expires_in;
+        self
+    }
+}
+
+impl TokenProvider for StaticTokenProvider {
+    fn fetch(&self, _force_refresh: bool) -> BoxFuture<'_, Result<ProvidedToken>> {
+        Box::pin(async move { Ok(self.token.clone()) })
+    }
+}
+
+/// HTTP 令牌提供者
+///
+/// 向中控服务发送 `GET` 请求，强制刷新时附加 `force_refresh=true` 查询参数。
+/// 中控服务应返回 `{"access_token": "...", "expires_in": 7200}`，
+/// 返回 `errcode`/`errmsg` 时按微信错误码解析。
+#[derive(Debug, Clone)]
+pub struct HttpTokenProvider {
+    url: String,
+    headers: HeaderMap,
+    client: reqwest::Client,
+}
+
+impl HttpTokenProvider {
+    pub fn new(url: &str) -> Self {
+        HttpTokenProvider {
+            url: url.into(),
+            headers: HeaderMap::new(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// 添加请求头，如中控服务的鉴权信息
+    ///
+    /// # 错误
+    ///
+    /// 请求头名称或值无效时返回 [`Error::InvalidParameter`]
+    pub fn header(mut self, name: &str, value: &str) -> Result<Self> {
+        let name = HeaderName::from_bytes(name.as_bytes())
+            .map_err(|e| Error::InvalidParameter(format!("无效的请求头名称: {}", e)))?;
+        let mut value = HeaderValue::from_str(value)
+            .map_err(|e| Error::InvalidParameter(format!("无效的请求头: {}", e)))?;
+        value.set_sensitive(true);
+
+        self.headers.insert(name, value);
+
+        Ok(self)
+    }
+
+    /// 使用自定义的 HTTP 客户端
+    pub fn http_client(mut self, client: reqwest::Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    #[instrument(skip(self), fields(endpoint = %self.url, status, errcode, rid))]
+    async fn request(&self, force_refresh: bool) -> Result<ProvidedToken> {
+        let mut request = self.client.get(&self.url).headers(self.headers.clone());
+
+        if force_refresh {
+            request = request.query(&[("force_refresh", "true")]);
+        }
+
+        let response = request.send().await?;
+
+        redact::response(&response);
+
+        if response.status().is_success() {
+            let response = response.json::<Response<ProvidedToken>>().await?;
+
+            let token = response.extract()?;
+
+            debug!(expires_in = token.expires_in, "provided token fetched");
+
+            Ok(token)
+        } else {
+            Err(Error::InternalServer(response.text().await?))
+        }
+    }
+}
+
+impl TokenProvider for HttpTokenProvider {
+    fn fetch(&self, force_refresh: bool) -> BoxFuture<'_, Result<ProvidedToken>> {
+        Box::pin(self.request(force_refresh))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_static_provider() {
+        let provider = StaticTokenProvider::new("token").expires_in(60);
+
+        let token = provider.fetch(true).await.unwrap();
+
+        assert_eq!(token.access_token.expose_secret(), "token");
+        assert_eq!(token.expires_in, 60);
+    }
+
+    #[test]
+    fn test_provided_token_response() {
+        let json = r#"{ "access_token": "token", "expires_in": 7200 }"#;
+        let token = serde_json::from_str::<Response<ProvidedToken>>(json)
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(token.expires_in, 7200);
+
+        let json = r#"{ "errcode": 40001, "errmsg": "invalid credential" }"#;
+        let result = serde_json::from_str::<Response<ProvidedToken>>(json)
+            .unwrap()
+            .extract();
+        assert!(matches!(result, Err(Error::InvalidCredential(_))));
+    }
+}
@@ -4,9 +4,10 @@
 //!
 //! # 主要功能
 //!
-//! - 解析用户加密数据（用户基本信息）
+//! - 解析用户加密数据（用户基本信息、微信运动步数、群聊信息、旧版手机号）
 //! - 获取用户手机号信息
-//! - 数据水印验证（确保数据来源可信）
+//! - 支付后获取 UnionID、获取插件用户 openpid
+//! - 数据水印验证（确保数据来源可信，参见 [`Client::verify_watermark`]）
 //!
 //! # 数据安全
 //!
@@ -33,11 +34,20 @@
 //! # }
 //! ```
 
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
+use serde_repr::{Deserialize_repr, Serialize_repr};
 use std::collections::HashMap;
-use tracing::debug;
+use tracing::{debug, instrument};
 
-use crate::{Result, client::Client, constants, error::Error::InternalServer, response::Response};
+use crate::{
+    Result,
+    client::Client,
+    constants,
+    error::Error::{self, InternalServer},
+    redact,
+    response::Response,
+};
 
 /// 微信用户基本信息
 ///
@@ -143,6 +153,80 @@ impl UserBuilder {
     }
 }
 
+/// 微信用户公开信息
+///
+/// 对应前端 `wx.getUserInfo()` 返回的明文 `rawData`，字段与 [`User`] 相同但不包含水印。
+/// 需要先通过 [`Credential::verify_user_info`] 校验签名。
+///
+/// [`Credential::verify_user_info`]: crate::Credential::verify_user_info
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UserInfo {
+    nickname: String,
+    gender: u8,
+    language: Option<String>,
+    country: String,
+    province: String,
+    city: String,
+    avatar: String,
+}
+
+impl UserInfo {
+    pub fn nickname(&self) -> &str {
+        &self.nickname
+    }
+
+    pub fn gender(&self) -> u8 {
+        self.gender
+    }
+
+    pub fn language(&self) -> Option<&str> {
+        self.language.as_deref()
+    }
+
+    pub fn country(&self) -> &str {
+        &self.country
+    }
+
+    pub fn province(&self) -> &str {
+        &self.province
+    }
+
+    pub fn city(&self) -> &str {
+        &self.city
+    }
+
+    pub fn avatar(&self) -> &str {
+        &self.avatar
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct UserInfoBuilder {
+    #[serde(rename = "nickName")]
+    nickname: String,
+    gender: u8,
+    language: Option<String>,
+    country: String,
+    province: String,
+    city: String,
+    #[serde(rename = "avatarUrl")]
+    avatar: String,
+}
+
+impl UserInfoBuilder {
+    pub(crate) fn build(self) -> UserInfo {
+        UserInfo {
+            nickname: self.nickname,
+            gender: self.gender,
+            language: self.language,
+            country: self.country,
+            province: self.province,
+            city: self.city,
+            avatar: self.avatar,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Contact {
     phone_number: String,
@@ -181,18 +265,14 @@ pub(crate) struct ContactBuilder {
 
 impl ContactBuilder {
     pub(crate) fn build(self) -> Contact {
-        Contact {
-            phone_number: self.inner.phone_number,
-            pure_phone_number: self.inner.pure_phone_number,
-            country_code: self.inner.country_code,
-            watermark: self.inner.watermark.build(),
-        }
+        self.inner.build()
     }
 }
 
+/// 旧版 `getPhoneNumber` 加密数据解密后的结构，与 `phone_info` 字段结构相同
 #[derive(Debug, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
-struct PhoneInner {
+pub(crate) struct PhoneInner {
     #[serde(rename = "phoneNumber")]
     phone_number: String,
     #[serde(rename = "purePhoneNumber")]
@@ -201,12 +281,335 @@ struct PhoneInner {
     watermark: WatermarkBuilder,
 }
 
+impl PhoneInner {
+    pub(crate) fn build(self) -> Contact {
+        Contact {
+            phone_number: self.phone_number,
+            pure_phone_number: self.pure_phone_number,
+            country_code: self.country_code,
+            watermark: self.watermark.build(),
+        }
+    }
+}
+
+/// 微信运动步数
+///
+/// 通过前端 `wx.getWeRunData()` 获取加密数据并解密得到，包含最近 30 天的步数。
+///
+/// # 示例
+///
+/// ```no_run
+/// use wechat_minapp::user::WeRunData;
+///
+/// # fn process(data: WeRunData) {
+/// for step in data.step_info_list() {
+///     println!("{}: {} 步", step.timestamp(), step.step());
+/// }
+/// # }
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WeRunData {
+    step_info_list: Vec<StepInfo>,
+    watermark: Watermark,
+}
+
+impl WeRunData {
+    pub fn step_info_list(&self) -> &[StepInfo] {
+        &self.step_info_list
+    }
+
+    pub fn app_id(&self) -> &str {
+        &self.watermark.app_id
+    }
+
+    pub fn timestamp(&self) -> u64 {
+        self.watermark.timestamp
+    }
+}
+
+/// 单日步数
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StepInfo {
+    timestamp: u64,
+    step: u32,
+}
+
+impl StepInfo {
+    /// 当天零点的时间戳（秒）
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    pub fn step(&self) -> u32 {
+        self.step
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WeRunDataBuilder {
+    step_info_list: Vec<StepInfo>,
+    watermark: WatermarkBuilder,
+}
+
+impl WeRunDataBuilder {
+    pub(crate) fn build(self) -> WeRunData {
+        WeRunData {
+            step_info_list: self.step_info_list,
+            watermark: self.watermark.build(),
+        }
+    }
+}
+
+/// 群聊信息
+///
+/// 通过前端 `wx.getShareInfo()` 或 `wx.getGroupEnterInfo()` 获取加密数据并解密得到。
+///
+/// # 字段说明
+///
+/// - `open_g_id`: 群对当前小程序的唯一 ID
+/// - `chat_type`: 聊天室类型，仅 `wx.getGroupEnterInfo()` 返回
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GroupInfo {
+    open_g_id: String,
+    chat_type: Option<ChatType>,
+    watermark: Watermark,
+}
+
+impl GroupInfo {
+    pub fn open_g_id(&self) -> &str {
+        &self.open_g_id
+    }
+
+    pub fn chat_type(&self) -> Option<ChatType> {
+        self.chat_type
+    }
+
+    pub fn app_id(&self) -> &str {
+        &self.watermark.app_id
+    }
+
+    pub fn timestamp(&self) -> u64 {
+        self.watermark.timestamp
+    }
+}
+
+/// 聊天室类型
+#[derive(Debug, Serialize_repr, Deserialize_repr, Clone, Copy, PartialEq)]
+#[repr(u8)]
+pub enum ChatType {
+    /// 微信联系人单聊
+    Single = 1,
+    /// 企业微信联系人单聊
+    WorkSingle = 2,
+    /// 普通微信群聊
+    Group = 3,
+    /// 企业微信互通群聊
+    WorkGroup = 4,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct GroupInfoBuilder {
+    #[serde(rename = "openGId", alias = "opengid")]
+    open_g_id: String,
+    chat_type: Option<ChatType>,
+    watermark: WatermarkBuilder,
+}
+
+impl GroupInfoBuilder {
+    pub(crate) fn build(self) -> GroupInfo {
+        GroupInfo {
+            open_g_id: self.open_g_id,
+            chat_type: self.chat_type,
+            watermark: self.watermark.build(),
+        }
+    }
+}
+
+/// 支付订单标识
+///
+/// 用于 [`Client::get_paid_union_id`]，可以使用微信支付订单号，
+/// 或微信支付商户号与商户订单号。
+#[derive(Debug, Clone)]
+pub enum PaidOrder {
+    /// 微信支付订单号
+    Transaction(String),
+    /// 微信支付商户号和商户订单号
+    MchOrder {
+        mch_id: String,
+        out_trade_no: String,
+    },
+}
+
+impl PaidOrder {
+    /// 订单标识对应的查询参数
+    fn query(&self) -> Vec<(&'static str, String)> {
+        match self {
+            PaidOrder::Transaction(transaction_id) => {
+                vec![("transaction_id", transaction_id.clone())]
+            }
+            PaidOrder::MchOrder {
+                mch_id,
+                out_trade_no,
+            } => vec![
+                ("mch_id", mch_id.clone()),
+                ("out_trade_no", out_trade_no.clone()),
+            ],
+        }
+    }
+}
+
+/// 支付后获取的用户 UnionID
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PaidUnionId {
+    union_id: String,
+}
+
+impl PaidUnionId {
+    pub fn union_id(&self) -> &str {
+        &self.union_id
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct PaidUnionIdBuilder {
+    #[serde(rename = "unionid")]
+    union_id: String,
+}
+
+impl PaidUnionIdBuilder {
+    pub(crate) fn build(self) -> PaidUnionId {
+        PaidUnionId {
+            union_id: self.union_id,
+        }
+    }
+}
+
+/// 插件用户 openpid
+///
+/// 插件用户在插件内的唯一标识，与小程序的 openid 不同。
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PluginOpenPid {
+    open_pid: String,
+}
+
+impl PluginOpenPid {
+    pub fn open_pid(&self) -> &str {
+        &self.open_pid
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct PluginOpenPidBody<'a> {
+    code: &'a str,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct PluginOpenPidBuilder {
+    #[serde(rename = "openpid")]
+    open_pid: String,
+}
+
+impl PluginOpenPidBuilder {
+    pub(crate) fn build(self) -> PluginOpenPid {
+        PluginOpenPid {
+            open_pid: self.open_pid,
+        }
+    }
+}
+
+/// 数据水印
+///
+/// 包含生成数据的小程序 AppID 和时间戳，可用于校验数据来源与时效性。
 #[derive(Debug, Serialize, Deserialize, Clone)]
-struct Watermark {
+pub struct Watermark {
     app_id: String,
     timestamp: u64,
 }
 
+impl Watermark {
+    pub fn app_id(&self) -> &str {
+        &self.app_id
+    }
+
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    /// 校验水印
+    ///
+    /// 检查水印中的 AppID 是否与 `app_id` 一致，且时间戳与当前时间相差不超过 `max_age`。
+    ///
+    /// # 错误
+    ///
+    /// 校验失败时返回 [`Error::InvalidWatermark`]
+    pub fn verify(&self, app_id: &str, max_age: Duration) -> Result<()> {
+        self.verify_at(app_id, max_age, Utc::now())
+    }
+
+    pub(crate) fn verify_at(
+        &self,
+        app_id: &str,
+        max_age: Duration,
+        now: DateTime<Utc>,
+    ) -> Result<()> {
+        if self.app_id != app_id {
+            return Err(Error::InvalidWatermark(format!(
+                "appid 不匹配: {}",
+                self.app_id
+            )));
+        }
+
+        let timestamp = i64::try_from(self.timestamp)
+            .ok()
+            .and_then(|ts| DateTime::<Utc>::from_timestamp(ts, 0))
+            .ok_or_else(|| Error::InvalidWatermark(format!("时间戳无效: {}", self.timestamp)))?;
+
+        let age = now.signed_duration_since(timestamp);
+
+        if age.abs() > max_age {
+            return Err(Error::InvalidWatermark(format!(
+                "时间戳超出有效期: {}",
+                self.timestamp
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// 携带数据水印的开放数据
+///
+/// [`User`]、[`Contact`]、[`WeRunData`]、[`GroupInfo`] 均实现了该 trait，
+/// 可以通过 [`Client::verify_watermark`] 校验。
+pub trait Watermarked {
+    fn watermark(&self) -> &Watermark;
+}
+
+impl Watermarked for User {
+    fn watermark(&self) -> &Watermark {
+        &self.watermark
+    }
+}
+
+impl Watermarked for Contact {
+    fn watermark(&self) -> &Watermark {
+        &self.watermark
+    }
+}
+
+impl Watermarked for WeRunData {
+    fn watermark(&self) -> &Watermark {
+        &self.watermark
+    }
+}
+
+impl Watermarked for GroupInfo {
+    fn watermark(&self) -> &Watermark {
+        &self.watermark
+    }
+}
+
 #[derive(Debug, Deserialize, Clone)]
 struct WatermarkBuilder {
     #[serde(rename = "appid")]
@@ -224,6 +627,34 @@ impl WatermarkBuilder {
 }
 
 impl Client {
+    /// 校验开放数据的水印
+    ///
+    /// 检查水印中的 AppID 是否为当前客户端的 AppID，且时间戳在
+    /// [`ClientBuilder::watermark_max_age`] 配置的时间窗口内（默认 10 分钟），
+    /// 用于拒绝其他小程序的数据或被重放的旧数据。
+    ///
+    /// # 示例
+    ///
+    /// ```no_run
+    /// use wechat_minapp::Client;
+    ///
+    /// # async fn example(client: Client) -> wechat_minapp::Result<()> {
+    /// let credential = client.login("code").await?;
+    /// let user = credential.decrypt("encrypted_data", "iv")?;
+    /// client.verify_watermark(&user)?;
+    ///
+    /// let contact = client.get_contact("phone_code", None).await?;
+    /// client.verify_watermark(&contact)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ClientBuilder::watermark_max_age`]: crate::ClientBuilder::watermark_max_age
+    pub fn verify_watermark(&self, data: &impl Watermarked) -> Result<()> {
+        data.watermark()
+            .verify_at(self.app_id(), self.watermark_max_age(), self.now())
+    }
+
     /// 获取用户手机号信息
     ///
     /// 通过前端获取的临时凭证 code 换取用户的手机号信息。
@@ -282,8 +713,16 @@ impl Client {
     /// # API 文档
     ///
     /// [获取手机号](https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/user-info/phone-number/getPhoneNumber.html)
+    #[instrument(
+        skip(self, code, open_id),
+        fields(endpoint = constants::PHONE_END_POINT, status, errcode, rid)
+    )]
     pub async fn get_contact(&self, code: &str, open_id: Option<&str>) -> Result<Contact> {
-        debug!("code: {}, open_id: {:?}", code, open_id);
+        debug!(
+            code = %redact::pii(code),
+            open_id = ?open_id.map(redact::id),
+            "get contact"
+        );
 
         let mut query = HashMap::new();
         let mut body = HashMap::new();
@@ -295,26 +734,281 @@ impl Client {
             body.insert("openid", open_id);
         }
 
-        let response = self
-            .request()
-            .post(constants::PHONE_END_POINT)
-            .query(&query)
-            .json(&body)
-            .send()
-            .await?;
+        self.guarded(constants::PHONE_END_POINT, async {
+            let response = self
+                .request()
+                .post(self.url(constants::PHONE_END_POINT))
+                .query(&query)
+                .json(&body)
+                .send()
+                .await?;
 
-        debug!("response: {:#?}", response);
+            redact::response(&response);
 
-        if response.status().is_success() {
-            let response = response.json::<Response<ContactBuilder>>().await?;
+            if response.status().is_success() {
+                let response = response.json::<Response<ContactBuilder>>().await?;
 
-            let builder = response.extract()?;
+                let builder = response.extract()?;
 
-            debug!("contact builder: {:#?}", builder);
+                Ok(builder.build())
+            } else {
+                Err(InternalServer(response.text().await?))
+            }
+        })
+        .await
+    }
 
-            Ok(builder.build())
-        } else {
-            Err(InternalServer(response.text().await?))
-        }
+    /// 支付后获取用户 UnionID
+    ///
+    /// 用户完成支付后，无需用户授权即可获取其 UnionID，支付后 5 分钟内有效。
+    ///
+    /// # 参数
+    ///
+    /// - `open_id`: 支付用户唯一标识
+    /// - `order`: 支付订单标识
+    ///
+    /// # 示例
+    ///
+    /// ```no_run
+    /// use wechat_minapp::{Client, user::PaidOrder};
+    ///
+    /// # async fn example(client: Client) -> wechat_minapp::Result<()> {
+    /// let order = PaidOrder::Transaction("transaction_id".to_string());
+    /// let paid = client.get_paid_union_id("user_openid", &order).await?;
+    /// println!("UnionID: {}", paid.union_id());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # API 文档
+    ///
+    /// [支付后获取 UnionID](https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/user-info/basic-info/getPaidUnionid.html)
+    #[instrument(
+        skip(self, open_id, order),
+        fields(
+            endpoint = constants::PAID_UNION_ID_END_POINT,
+            open_id = %redact::id(open_id),
+            status,
+            errcode,
+            rid
+        )
+    )]
+    pub async fn get_paid_union_id(&self, open_id: &str, order: &PaidOrder) -> Result<PaidUnionId> {
+        let mut query = HashMap::new();
+
+        query.insert("access_token", self.token().await?);
+        query.insert("openid", open_id.to_string());
+
+        query.extend(order.query());
+
+        self.guarded(constants::PAID_UNION_ID_END_POINT, async {
+            let response = self
+                .request()
+                .get(self.url(constants::PAID_UNION_ID_END_POINT))
+                .query(&query)
+                .send()
+                .await?;
+
+            redact::response(&response);
+
+            if response.status().is_success() {
+                let response = response.json::<Response<PaidUnionIdBuilder>>().await?;
+
+                let builder = response.extract()?;
+
+                Ok(builder.build())
+            } else {
+                Err(InternalServer(response.text().await?))
+            }
+        })
+        .await
+    }
+
+    /// 获取插件用户 openpid
+    ///
+    /// 通过插件内 `wx.pluginLogin()` 获取的 code 换取插件用户的唯一标识。
+    ///
+    /// # API 文档
+    ///
+    /// [获取插件用户 openpid](https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/user-info/basic-info/getPluginOpenPId.html)
+    #[instrument(
+        skip(self, code),
+        fields(endpoint = constants::PLUGIN_OPEN_PID_END_POINT, status, errcode, rid)
+    )]
+    pub async fn get_plugin_open_pid(&self, code: &str) -> Result<PluginOpenPid> {
+        let mut query = HashMap::new();
+
+        query.insert("access_token", self.token().await?);
+
+        let body = PluginOpenPidBody { code };
+
+        self.guarded(constants::PLUGIN_OPEN_PID_END_POINT, async {
+            let response = self
+                .request()
+                .post(self.url(constants::PLUGIN_OPEN_PID_END_POINT))
+                .query(&query)
+                .json(&body)
+                .send()
+                .await?;
+
+            redact::response(&response);
+
+            if response.status().is_success() {
+                let response = response.json::<Response<PluginOpenPidBuilder>>().await?;
+
+                let builder = response.extract()?;
+
+                Ok(builder.build())
+            } else {
+                Err(InternalServer(response.text().await?))
+            }
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paid_order_query() {
+        let order = PaidOrder::Transaction("420000".into());
+        assert_eq!(order.query(), [("transaction_id", "420000".to_string())]);
+
+        let order = PaidOrder::MchOrder {
+            mch_id: "1230000109".into(),
+            out_trade_no: "20150806125346".into(),
+        };
+        assert_eq!(
+            order.query(),
+            [
+                ("mch_id", "1230000109".to_string()),
+                ("out_trade_no", "20150806125346".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_paid_union_id() {
+        let json = r#"{ "unionid": "oTmHYjg-tElZ68xxxxxxxxhy1Rgk", "errcode": 0, "errmsg": "ok" }"#;
+
+        let paid = serde_json::from_str::<Response<PaidUnionIdBuilder>>(json)
+            .unwrap()
+            .extract()
+            .unwrap()
+            .build();
+        assert_eq!(paid.union_id(), "oTmHYjg-tElZ68xxxxxxxxhy1Rgk");
+
+        let json = r#"{ "errcode": 40097, "errmsg": "invalid args" }"#;
+        let result = serde_json::from_str::<Response<PaidUnionIdBuilder>>(json)
+            .unwrap()
+            .extract();
+        assert!(matches!(result, Err(Error::InvalidParameter(_))));
+    }
+
+    #[test]
+    fn test_plugin_open_pid() {
+        let json = r#"{ "errcode": 0, "errmsg": "ok", "openpid": "GACo74wkDIkDzEhkwRwgjGt1pqlk" }"#;
+
+        let open_pid = serde_json::from_str::<Response<PluginOpenPidBuilder>>(json)
+            .unwrap()
+            .extract()
+            .unwrap()
+            .build();
+        assert_eq!(open_pid.open_pid(), "GACo74wkDIkDzEhkwRwgjGt1pqlk");
+
+        let body = serde_json::to_string(&PluginOpenPidBody { code: "code" }).unwrap();
+        assert_eq!(body, r#"{"code":"code"}"#);
+    }
+
+    #[test]
+    fn test_we_run_data() {
+        let json = r#"
+        {
+            "stepInfoList": [
+                { "timestamp": 1445866601, "step": 100 },
+                { "timestamp": 1445876601, "step": 120 }
+            ],
+            "watermark": { "appid": "wx4f4bc4dec97d474b", "timestamp": 1477314187 }
+        }"#;
+
+        let data = serde_json::from_str::<WeRunDataBuilder>(json)
+            .unwrap()
+            .build();
+
+        assert_eq!(data.step_info_list().len(), 2);
+        assert_eq!(data.step_info_list()[1].step(), 120);
+        assert_eq!(data.app_id(), "wx4f4bc4dec97d474b");
+    }
+
+    #[test]
+    fn test_group_info() {
+        let share = r#"
+        {
+            "openGId": "OPENGID",
+            "watermark": { "appid": "APPID", "timestamp": 1477314187 }
+        }"#;
+
+        let group = serde_json::from_str::<GroupInfoBuilder>(share)
+            .unwrap()
+            .build();
+
+        assert_eq!(group.open_g_id(), "OPENGID");
+        assert_eq!(group.chat_type(), None);
+
+        let enter = r#"
+        {
+            "opengid": "OPENGID",
+            "chat_type": 3,
+            "watermark": { "appid": "APPID", "timestamp": 1477314187 }
+        }"#;
+
+        let group = serde_json::from_str::<GroupInfoBuilder>(enter)
+            .unwrap()
+            .build();
+
+        assert_eq!(group.chat_type(), Some(ChatType::Group));
+    }
+
+    #[test]
+    fn test_legacy_contact() {
+        let json = r#"
+        {
+            "phoneNumber": "+86 13800000000",
+            "purePhoneNumber": "13800000000",
+            "countryCode": "86",
+            "watermark": { "appid": "APPID", "timestamp": 1477314187 }
+        }"#;
+
+        let contact = serde_json::from_str::<PhoneInner>(json).unwrap().build();
+
+        assert_eq!(contact.pure_phone_number(), "13800000000");
+        assert_eq!(contact.country_code(), "86");
+    }
+
+    #[test]
+    fn test_watermark_verify() {
+        let now = Utc::now().timestamp() as u64;
+        let watermark = Watermark {
+            app_id: "APPID".into(),
+            timestamp: now,
+        };
+
+        assert!(watermark.verify("APPID", Duration::minutes(10)).is_ok());
+        assert!(matches!(
+            watermark.verify("OTHER", Duration::minutes(10)),
+            Err(Error::InvalidWatermark(_))
+        ));
+
+        let stale = Watermark {
+            app_id: "APPID".into(),
+            timestamp: now - 3600,
+        };
+
+        assert!(matches!(
+            stale.verify("APPID", Duration::minutes(10)),
+            Err(Error::InvalidWatermark(_))
+        ));
     }
 }
@@ -0,0 +1,348 @@
+//! 微信小程序用户数据存储模块
+//!
+//! 该模块提供用户加密密钥和用户托管数据相关的接口，这些接口均需要使用
+//! session_key 对请求签名，签名规则参见 [`signature`](crate::signature) 模块。
+//!
+//! # 主要功能
+//!
+//! - 获取用户加密密钥（用于 `wx.getUserCryptoManager` 等加密场景）
+//! - 设置、删除用户托管数据（对应 `wx.setUserCloudStorage` 等 key-value 数据）
+//!
+//! # 快速开始
+//!
+//! ```no_run
+//! use wechat_minapp::{Client, user_storage::KeyValue};
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let client = Client::new("app_id", "secret");
+//!     let credential = client.login("code").await?;
+//!
+//!     let session_key = credential.session_key().expose_secret();
+//!
+//!     let keys = client
+//!         .get_user_encrypt_key(session_key, credential.open_id())
+//!         .await?;
+//!
+//!     let kv_list = vec![KeyValue::new("score", "100")];
+//!     client
+//!         .set_user_storage(session_key, credential.open_id(), &kv_list)
+//!         .await?;
+//!
+//!     Ok(())
+//! }
+//! ```
+
+use reqwest::header::{CONTENT_TYPE, HeaderValue};
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::HashMap;
+use tracing::instrument;
+
+use crate::{
+    Result,
+    client::Client,
+    constants,
+    error::Error::InternalServer,
+    redact,
+    response::{Response, Status},
+    secret::SecretString,
+    signature,
+};
+
+/// 用户加密密钥
+///
+/// 由 [`Client::get_user_encrypt_key`] 返回，微信最多返回最近 3 次的密钥。
+///
+/// 序列化时包含加密密钥明文，以便下发给小程序前端。
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UserEncryptKey {
+    #[serde(serialize_with = "UserEncryptKey::serialize_encrypt_key")]
+    encrypt_key: SecretString,
+    /// 密钥版本
+    pub version: u32,
+    /// 剩余有效时间（秒）
+    pub expire_in: i64,
+    /// 加密初始向量
+    pub iv: String,
+    /// 密钥创建时间戳（秒）
+    pub create_time: i64,
+}
+
+impl UserEncryptKey {
+    /// 加密密钥
+    pub fn encrypt_key(&self) -> &SecretString {
+        &self.encrypt_key
+    }
+
+    fn serialize_encrypt_key<S>(
+        encrypt_key: &SecretString,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        encrypt_key.expose_secret().serialize(serializer)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct UserEncryptKeyList {
+    key_info_list: Vec<UserEncryptKey>,
+}
+
+/// 用户托管数据键值对
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct KeyValue {
+    pub key: String,
+    pub value: String,
+}
+
+impl KeyValue {
+    pub fn new(key: impl Into<String>, value: impl Into<String>) -> Self {
+        KeyValue {
+            key: key.into(),
+            value: value.into(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct SetUserStorageBody<'a> {
+    kv_list: &'a [KeyValue],
+}
+
+#[derive(Debug, Serialize)]
+struct RemoveUserStorageBody<'a> {
+    key: &'a [&'a str],
+}
+
+impl Client {
+    /// 获取用户加密密钥
+    ///
+    /// 返回的密钥列表按版本从新到旧排列。
+    ///
+    /// # 参数
+    ///
+    /// - `session_key`: 用户的 session_key，用于签名
+    /// - `open_id`: 用户的 openid
+    ///
+    /// # API 文档
+    ///
+    /// [获取用户encryptKey](https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/user-info/internet/getUserEncryptKey.html)
+    #[instrument(
+        skip(self, session_key, open_id),
+        fields(
+            endpoint = constants::USER_ENCRYPT_KEY_END_POINT,
+            open_id = %redact::id(open_id),
+            status,
+            errcode,
+            rid
+        )
+    )]
+    pub async fn get_user_encrypt_key(
+        &self,
+        session_key: &str,
+        open_id: &str,
+    ) -> Result<Vec<UserEncryptKey>> {
+        let mut query = HashMap::new();
+
+        query.insert("access_token", self.token().await?);
+        query.insert("openid", open_id.to_string());
+        query.insert("signature", signature::hmac_sha256(session_key, b"")?);
+        query.insert("sig_method", signature::SIG_METHOD.into());
+
+        self.guarded(constants::USER_ENCRYPT_KEY_END_POINT, async {
+            let response = self
+                .request()
+                .post(self.url(constants::USER_ENCRYPT_KEY_END_POINT))
+                .query(&query)
+                .send()
+                .await?;
+
+            redact::response(&response);
+
+            if response.status().is_success() {
+                let response = response.json::<Response<UserEncryptKeyList>>().await?;
+
+                Ok(response.extract()?.key_info_list)
+            } else {
+                Err(InternalServer(response.text().await?))
+            }
+        })
+        .await
+    }
+
+    /// 设置用户托管数据
+    ///
+    /// 请求体使用 session_key 签名，签名内容必须与发送的请求体完全一致。
+    ///
+    /// # API 文档
+    ///
+    /// [设置用户托管数据](https://developers.weixin.qq.com/minigame/dev/api-backend/open-api/data/storage.setUserStorage.html)
+    #[instrument(
+        skip(self, session_key, open_id, kv_list),
+        fields(
+            endpoint = constants::SET_USER_STORAGE_END_POINT,
+            open_id = %redact::id(open_id),
+            status,
+            errcode,
+            rid
+        )
+    )]
+    pub async fn set_user_storage(
+        &self,
+        session_key: &str,
+        open_id: &str,
+        kv_list: &[KeyValue],
+    ) -> Result<()> {
+        let body = serde_json::to_string(&SetUserStorageBody { kv_list })?;
+
+        self.signed_storage_request(
+            constants::SET_USER_STORAGE_END_POINT,
+            session_key,
+            open_id,
+            body,
+        )
+        .await
+    }
+
+    /// 删除用户托管数据
+    ///
+    /// # API 文档
+    ///
+    /// [删除用户托管数据](https://developers.weixin.qq.com/minigame/dev/api-backend/open-api/data/storage.removeUserStorage.html)
+    #[instrument(
+        skip(self, session_key, open_id, keys),
+        fields(
+            endpoint = constants::REMOVE_USER_STORAGE_END_POINT,
+            open_id = %redact::id(open_id),
+            status,
+            errcode,
+            rid
+        )
+    )]
+    pub async fn remove_user_storage(
+        &self,
+        session_key: &str,
+        open_id: &str,
+        keys: &[&str],
+    ) -> Result<()> {
+        let body = serde_json::to_string(&RemoveUserStorageBody { key: keys })?;
+
+        self.signed_storage_request(
+            constants::REMOVE_USER_STORAGE_END_POINT,
+            session_key,
+            open_id,
+            body,
+        )
+        .await
+    }
+
+    async fn signed_storage_request(
+        &self,
+        endpoint: &'static str,
+        session_key: &str,
+        open_id: &str,
+        body: String,
+    ) -> Result<()> {
+        let mut query = HashMap::new();
+
+        query.insert("access_token", self.token().await?);
+        query.insert("openid", open_id.to_string());
+        query.insert(
+            "signature",
+            signature::hmac_sha256(session_key, body.as_bytes())?,
+        );
+        query.insert("sig_method", signature::SIG_METHOD.into());
+
+        self.guarded(endpoint, async {
+            let response = self
+                .request()
+                .post(self.url(endpoint))
+                .header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
+                .query(&query)
+                .body(body)
+                .send()
+                .await?;
+
+            redact::response(&response);
+
+            if response.status().is_success() {
+                let response = response.json::<Status>().await?;
+
+                response.extract()
+            } else {
+                Err(InternalServer(response.text().await?))
+            }
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+
+    #[test]
+    fn test_storage_body() {
+        let kv_list = vec![KeyValue::new("1", "0")];
+        let body = serde_json::to_string(&SetUserStorageBody { kv_list: &kv_list }).unwrap();
+        assert_eq!(body, r#"{"kv_list":[{"key":"1","value":"0"}]}"#);
+
+        let body = serde_json::to_string(&RemoveUserStorageBody { key: &["1", "2"] }).unwrap();
+        assert_eq!(body, r#"{"key":["1","2"]}"#);
+    }
+
+    #[test]
+    fn test_storage_status() {
+        let ok = serde_json::from_str::<Status>(r#"{ "errcode": 0, "errmsg": "ok" }"#).unwrap();
+        assert!(ok.extract().is_ok());
+
+        let json = r#"{ "errcode": 87009, "errmsg": "invalid signature" }"#;
+        let result = serde_json::from_str::<Status>(json).unwrap().extract();
+        assert!(matches!(result, Err(Error::InvalidSignature(_))));
+
+        // 未在 ErrorCode 中定义的错误码不能被当作成功
+        let json = r#"{ "errcode": 42001, "errmsg": "access_token expired" }"#;
+        let result = serde_json::from_str::<Status>(json).unwrap().extract();
+        assert!(matches!(result, Err(Error::InternalServer(_))));
+    }
+
+    #[test]
+    fn test_user_encrypt_key_list() {
+        let json = r#"
+        {
+            "errcode": 0,
+            "errmsg": "ok",
+            "key_info_list": [
+                {
+                    "encrypt_key": "VI6BpyrK9XH4i4AIGe86tg==",
+                    "version": 10,
+                    "expire_in": 3597,
+                    "iv": "6003f73ec441c386",
+                    "create_time": 1616572301
+                }
+            ]
+        }"#;
+
+        let keys = serde_json::from_str::<Response<UserEncryptKeyList>>(json)
+            .unwrap()
+            .extract()
+            .unwrap()
+            .key_info_list;
+
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].version, 10);
+        assert_eq!(
+            keys[0].encrypt_key().expose_secret(),
+            "VI6BpyrK9XH4i4AIGe86tg=="
+        );
+        assert!(!format!("{:?}", keys[0]).contains("VI6BpyrK9XH4i4AIGe86tg=="));
+        assert!(
+            serde_json::to_string(&keys[0])
+                .unwrap()
+                .contains(r#""encrypt_key":"VI6BpyrK9XH4i4AIGe86tg==""#)
+        );
+    }
+}